@@ -6,16 +6,16 @@
 
 use std::cmp;
 use std::mem;
+use std::vec;
 #[cfg(feature = "unstable")]
 use std::num::One;
-#[cfg(feature = "unstable")]
-use std::ops::Add;
+use std::ops::{Add, Sub, Mul, Div};
 use std::iter::{Fuse, Peekable};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::Hash;
 use Itertools;
 use size_hint;
-use misc::MendSlice;
+use misc::{MendSlice, Gcd, ToFloat, TupleCollect};
 
 macro_rules! clone_fields {
     ($name:ident, $base:expr, $($field:ident),+) => (
@@ -71,6 +71,28 @@ impl<I, J> Iterator for Interleave<I, J> where
             }
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint::add(self.a.size_hint(), self.b.size_hint())
+    }
+}
+
+impl<I, J> DoubleEndedIterator for Interleave<I, J> where
+    I: DoubleEndedIterator + ExactSizeIterator,
+    J: DoubleEndedIterator<Item=I::Item> + ExactSizeIterator,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<I::Item> {
+        // The last element comes from the longer iterator, and if they have
+        // equal length, from the one whose turn is last.
+        let (a_len, b_len) = (self.a.len(), self.b.len());
+        let a_next = !self.flag;
+        if (a_next && a_len > b_len) || (!a_next && a_len >= b_len) {
+            self.a.next_back()
+        } else {
+            self.b.next_back()
+        }
+    }
 }
 
 /// An iterator adaptor that alternates elements from the two iterators until
@@ -203,6 +225,19 @@ impl<I> Iterator for PutBack<I> where
     }
 }
 
+impl<I> DoubleEndedIterator for PutBack<I> where
+    I: DoubleEndedIterator,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<I::Item> {
+        // the put back value is at the front, so it comes last
+        match self.iter.next_back() {
+            None => self.top.take(),
+            some => some,
+        }
+    }
+}
+
 /// An iterator adaptor that allows putting multiple
 /// items in front of the iterator.
 ///
@@ -276,6 +311,9 @@ impl<I: Iterator> Clone for PutBackN<I> where
 ///
 /// Iterator element type is `(I::Item, J::Item)`.
 ///
+/// When `I` and `J` are double ended, the product can be iterated from
+/// both ends; the two ends meet without yielding any pair twice.
+///
 /// See [*.cartesian_product()*](trait.Itertools.html#method.cartesian_product) for more information.
 pub struct Product<I, J> where
     I: Iterator,
@@ -284,6 +322,8 @@ pub struct Product<I, J> where
     a_cur: Option<I::Item>,
     b: J,
     b_orig: J,
+    /// The last row, when iterating from the back
+    back: Option<(I::Item, J)>,
 }
 
 impl<I, J> Product<I, J> where
@@ -297,7 +337,7 @@ impl<I, J> Product<I, J> where
     pub fn new(i: I, j: J) -> Self
     {
         let mut i = i;
-        Product{a_cur: i.next(), a: i, b: j.clone(), b_orig: j}
+        Product{a_cur: i.next(), a: i, b: j.clone(), b_orig: j, back: None}
     }
 }
 
@@ -317,7 +357,21 @@ impl<I, J> Iterator for Product<I, J> where
                     None => return None,
                     Some(x) => {
                         self.a_cur = self.a.next();
-                        x
+                        match self.back.take() {
+                            // continue with what is left of the last row
+                            Some((a, b)) if self.a_cur.is_none() => {
+                                self.a_cur = Some(a);
+                                self.b = b;
+                                match self.b.next() {
+                                    None => return None,
+                                    Some(y) => y,
+                                }
+                            }
+                            back => {
+                                self.back = back;
+                                x
+                            }
+                        }
                     }
                 }
             }
@@ -333,14 +387,58 @@ impl<I, J> Iterator for Product<I, J> where
 
     fn size_hint(&self) -> (usize, Option<usize>)
     {
-        let has_cur = self.a_cur.is_some() as usize;
         // Not ExactSizeIterator because size may be larger than usize
-        let (b, _) = self.b.size_hint();
+        let b = match self.a_cur {
+            Some(_) => self.b.size_hint(),
+            None => (0, Some(0)),
+        };
+        let back = match self.back {
+            Some((_, ref b_back)) => b_back.size_hint(),
+            None => (0, Some(0)),
+        };
+
+        // Compute a * b_orig + b + b_back for both lower and upper bound.
+        // On overflow the lower bound saturates at usize::MAX, which is
+        // still correct: the true length is then larger than usize::MAX.
+        size_hint::add(
+            size_hint::add(
+                size_hint::mul(self.a.size_hint(), self.b_orig.size_hint()),
+                b),
+            back)
+    }
+}
 
-        // Compute a * b_orig + b for both lower and upper bound
-        size_hint::add_scalar(
-            size_hint::mul(self.a.size_hint(), self.b_orig.size_hint()),
-            b * has_cur)
+impl<I, J> DoubleEndedIterator for Product<I, J> where
+    I: DoubleEndedIterator,
+    J: Clone + DoubleEndedIterator,
+    I::Item: Clone,
+{
+    fn next_back(&mut self) -> Option<(I::Item, J::Item)>
+    {
+        if let Some((ref a, ref mut b)) = self.back {
+            if let Some(y) = b.next_back() {
+                return Some((a.clone(), y));
+            }
+        }
+        match self.a.next_back() {
+            Some(a) => {
+                let mut b = self.b_orig.clone();
+                let y = match b.next_back() {
+                    None => return None,
+                    Some(y) => y,
+                };
+                self.back = Some((a.clone(), b));
+                Some((a, y))
+            }
+            None => {
+                // only the current row is left
+                self.back = None;
+                match self.a_cur {
+                    None => None,
+                    Some(ref a) => self.b.next_back().map(|y| (a.clone(), y)),
+                }
+            }
+        }
     }
 }
 
@@ -354,13 +452,27 @@ impl<I, J> Iterator for Product<I, J> where
 pub struct Batching<I, F> {
     f: F,
     iter: I,
+    hint: Option<fn((usize, Option<usize>)) -> (usize, Option<usize>)>,
 }
 
 impl<F, I> Batching<I, F> {
     /// Create a new Batching iterator.
     pub fn new(iter: I, f: F) -> Batching<I, F>
     {
-        Batching{f: f, iter: iter}
+        Batching{f: f, iter: iter, hint: None}
+    }
+
+    /// Create a new Batching iterator whose size hint is computed by
+    /// `hint` from the size hint of the underlying iterator.
+    ///
+    /// `hint` must be consistent with how `f` consumes the iterator.
+    pub fn with_size_hint<B>(iter: I, f: F,
+                             hint: fn((usize, Option<usize>)) -> (usize, Option<usize>))
+        -> Batching<I, F>
+        where I: Iterator,
+              F: FnMut(&mut I) -> Option<B>,
+    {
+        Batching{f: f, iter: iter, hint: Some(hint)}
     }
 }
 
@@ -378,8 +490,11 @@ impl<B, F, I> Iterator for Batching<I, F> where
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>)
     {
-        // No information about closue behavior
-        (0, None)
+        match self.hint {
+            Some(hint) => hint(self.iter.size_hint()),
+            // No information about closue behavior
+            None => (0, None),
+        }
     }
 }
 
@@ -449,6 +564,64 @@ impl<K, I, F> Iterator for GroupBy<K, I, F> where
     }
 }
 
+/// An iterator adaptor that counts iterator elements in groups. Consecutive
+/// elements that map to the same key (“runs”), are counted together.
+///
+/// Iterator element type is `(K, usize)`.
+///
+/// See [*.group_by_count()*](trait.Itertools.html#method.group_by_count) for more information.
+#[derive(Clone)]
+pub struct GroupByCount<K, I, F> {
+    key: F,
+    iter: I,
+    current: Option<(K, usize)>,
+}
+
+impl<K, F, I> GroupByCount<K, I, F> where
+    I: Iterator,
+{
+    /// Create a new `GroupByCount` iterator.
+    pub fn new(iter: I, key: F) -> Self
+    {
+        GroupByCount{key: key, iter: iter, current: None}
+    }
+}
+
+impl<K, I, F> Iterator for GroupByCount<K, I, F> where
+    K: PartialEq,
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = (K, usize);
+    fn next(&mut self) -> Option<(K, usize)>
+    {
+        for elt in self.iter.by_ref() {
+            let key = (self.key)(&elt);
+            match self.current.take() {
+                None => self.current = Some((key, 1)),
+                Some((old_key, n)) => if old_key != key {
+                    self.current = Some((key, 1));
+                    return Some((old_key, n))
+                } else {
+                    self.current = Some((old_key, n + 1));
+                },
+            }
+        }
+        self.current.take()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        let stored_count = self.current.is_some() as usize;
+        let mut sh = size_hint::add_scalar(self.iter.size_hint(),
+                                           stored_count);
+        if sh.0 > 0 {
+            sh.0 = 1;
+        }
+        sh
+    }
+}
+
 /// An iterator adaptor that steps a number elements in the base iterator
 /// for each iteration.
 ///
@@ -567,7 +740,7 @@ impl<I, J> MergeCore<I, J>
 ///
 /// Iterator element type is `I::Item`.
 ///
-/// See [*.merge()*](trait.Itertools.html#method.merge_by) for more information.
+/// See [*.merge()*](trait.Itertools.html#method.merge) for more information.
 pub struct Merge<I, J> where
     I: Iterator,
     J: Iterator<Item=I::Item>,
@@ -766,6 +939,12 @@ impl<I: Iterator> MultiPeek<I> {
         self.index += 1;
         ret
     }
+
+    /// Reset the peeking “cursor”, so that the next *.peek()* returns the
+    /// same element as *.next()* would, without consuming anything.
+    pub fn reset_peek(&mut self) {
+        self.index = 0;
+    }
 }
 
 impl<I> Iterator for MultiPeek<I> where
@@ -888,13 +1067,15 @@ pub struct Dedup<I>
     where I: Iterator,
 {
     iter: CoalesceCore<I>,
+    /// The pending element of the last run, when iterating from the back
+    last_back: Option<I::Item>,
 }
 
 impl<I: Clone> Clone for Dedup<I>
     where I: Iterator, I::Item: Clone
 {
     fn clone(&self) -> Self {
-        clone_fields!(Dedup, self, iter)
+        clone_fields!(Dedup, self, iter, last_back)
     }
 }
 
@@ -908,6 +1089,7 @@ impl<I> Dedup<I> where
                 last: iter.next(),
                 iter: iter,
             },
+            last_back: None,
         }
     }
 }
@@ -919,8 +1101,105 @@ impl<I> Iterator for Dedup<I>
     type Item = I::Item;
 
     fn next(&mut self) -> Option<I::Item> {
-        self.iter.next_with(|x, y| {
+        let elt = self.iter.next_with(|x, y| {
             if x == y { Ok(x) } else { Err((x, y)) }
+        });
+        match elt {
+            None => self.last_back.take(),
+            Some(elt) => {
+                if self.iter.last.is_none() {
+                    // the iterator is exhausted; continue with the last run,
+                    // unless it is the same as this one
+                    match self.last_back.take() {
+                        Some(back) => if back != elt { self.iter.last = Some(back) },
+                        None => {}
+                    }
+                }
+                Some(elt)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, hi) = self.iter.size_hint();
+        let back = self.last_back.is_some() as usize;
+        (cmp::max(low, back), hi.and_then(|hi| hi.checked_add(back)))
+    }
+}
+
+impl<I> DoubleEndedIterator for Dedup<I>
+    where I: DoubleEndedIterator,
+          I::Item: PartialEq,
+{
+    fn next_back(&mut self) -> Option<I::Item> {
+        while let Some(elt) = self.iter.iter.next_back() {
+            match self.last_back.take() {
+                Some(back) => if back != elt {
+                    self.last_back = Some(elt);
+                    return Some(back);
+                } else {
+                    self.last_back = Some(back);
+                },
+                None => self.last_back = Some(elt),
+            }
+        }
+        // the iterator is exhausted; only the first and last runs are left
+        match (self.iter.last.take(), self.last_back.take()) {
+            (Some(front), Some(back)) => if front == back {
+                Some(front)
+            } else {
+                self.iter.last = Some(front);
+                Some(back)
+            },
+            (front, back) => back.or(front),
+        }
+    }
+}
+
+/// An iterator adaptor that removes repeated duplicates, using a closure
+/// to decide whether two elements are equal.
+///
+/// See [*.dedup_by()*](trait.Itertools.html#method.dedup_by) for more information.
+pub struct DedupBy<I, F>
+    where I: Iterator,
+{
+    iter: CoalesceCore<I>,
+    same: F,
+}
+
+impl<I: Clone, F: Clone> Clone for DedupBy<I, F>
+    where I: Iterator, I::Item: Clone
+{
+    fn clone(&self) -> Self {
+        clone_fields!(DedupBy, self, iter, same)
+    }
+}
+
+impl<I, F> DedupBy<I, F> where
+    I: Iterator,
+{
+    /// Create a new `DedupBy`.
+    pub fn new(mut iter: I, same: F) -> Self {
+        DedupBy {
+            iter: CoalesceCore {
+                last: iter.next(),
+                iter: iter,
+            },
+            same: same,
+        }
+    }
+}
+
+impl<I, F> Iterator for DedupBy<I, F>
+    where I: Iterator,
+          F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let same = &mut self.same;
+        self.iter.next_with(|x, y| {
+            if same(&x, &y) { Ok(x) } else { Err((x, y)) }
         })
     }
 
@@ -929,6 +1208,60 @@ impl<I> Iterator for Dedup<I>
     }
 }
 
+/// An iterator adaptor that removes repeated duplicates, counting the
+/// elements of each run.
+///
+/// See [*.dedup_with_count()*](trait.Itertools.html#method.dedup_with_count) for more information.
+#[derive(Clone)]
+pub struct DedupWithCount<I>
+    where I: Iterator,
+{
+    iter: I,
+    last: Option<(usize, I::Item)>,
+}
+
+impl<I> DedupWithCount<I> where
+    I: Iterator,
+{
+    /// Create a new `DedupWithCount`.
+    pub fn new(mut iter: I) -> Self {
+        DedupWithCount {
+            last: iter.next().map(|x| (1, x)),
+            iter: iter,
+        }
+    }
+}
+
+impl<I> Iterator for DedupWithCount<I>
+    where I: Iterator,
+          I::Item: PartialEq,
+{
+    type Item = (usize, I::Item);
+
+    fn next(&mut self) -> Option<(usize, I::Item)> {
+        // this fuses the iterator
+        let (mut count, last) = match self.last.take() {
+            None => return None,
+            Some(x) => x,
+        };
+        for next in &mut self.iter {
+            if next == last {
+                count += 1;
+            } else {
+                self.last = Some((1, next));
+                return Some((count, last));
+            }
+        }
+        Some((count, last))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, hi) = size_hint::add_scalar(self.iter.size_hint(),
+                                              self.last.is_some() as usize);
+        ((low > 0) as usize, hi)
+    }
+}
+
 /// An iterator adaptor that glues together adjacent contiguous slices.
 ///
 /// See [*.mend_slices()*](trait.Itertools.html#method.mend_slices) for more information.
@@ -1121,7 +1454,7 @@ impl<I> Iterator for Combinations<I> where I: Iterator + Clone, I::Item: Clone{
 
 /// An iterator adapter to filter out duplicate elements.
 ///
-/// See [*.unique_by()*](trait.Itertools.html#method.unique) for more information.
+/// See [*.unique_by()*](trait.Itertools.html#method.unique_by) for more information.
 #[derive(Clone)]
 pub struct UniqueBy<I: Iterator, V, F> {
     iter: I,
@@ -1219,3 +1552,1854 @@ pub fn unique<I>(iter: I) -> Unique<I>
         }
     }
 }
+
+/// An iterator adaptor that yields a head element, then all elements
+/// of the base iterator, then a tail element.
+///
+/// Iterator element type is `I::Item`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.surround()*](trait.Itertools.html#method.surround) for more information.
+#[derive(Clone)]
+pub struct Surround<I> where
+    I: Iterator,
+{
+    head: Option<I::Item>,
+    iter: Fuse<I>,
+    tail: Option<I::Item>,
+}
+
+impl<I> Surround<I> where
+    I: Iterator,
+{
+    /// Create a new `Surround` iterator.
+    pub fn new(iter: I, head: I::Item, tail: I::Item) -> Self
+    {
+        Surround {
+            head: Some(head),
+            iter: iter.fuse(),
+            tail: Some(tail),
+        }
+    }
+}
+
+impl<I> Iterator for Surround<I> where
+    I: Iterator,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item>
+    {
+        if self.head.is_some() {
+            return self.head.take();
+        }
+        match self.iter.next() {
+            None => self.tail.take(),
+            elt => elt,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        let extra = self.head.is_some() as usize + self.tail.is_some() as usize;
+        size_hint::add_scalar(self.iter.size_hint(), extra)
+    }
+}
+
+impl<I> ExactSizeIterator for Surround<I> where
+    I: ExactSizeIterator,
+{ }
+
+/// An iterator adaptor that yields the greatest common divisor of
+/// all elements seen so far.
+///
+/// See [*.running_gcd()*](trait.Itertools.html#method.running_gcd) for more information.
+#[derive(Clone)]
+pub struct RunningGcd<I> where
+    I: Iterator,
+{
+    iter: I,
+    acc: Option<I::Item>,
+}
+
+impl<I> RunningGcd<I> where
+    I: Iterator,
+{
+    /// Create a new `RunningGcd` iterator.
+    pub fn new(iter: I) -> Self
+    {
+        RunningGcd { iter: iter, acc: None }
+    }
+}
+
+impl<I> Iterator for RunningGcd<I> where
+    I: Iterator,
+    I::Item: Gcd,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item>
+    {
+        let elt = match self.iter.next() {
+            None => return None,
+            Some(x) => x,
+        };
+        let acc = match self.acc {
+            None => elt.gcd(elt),
+            Some(acc) => acc.gcd(elt),
+        };
+        self.acc = Some(acc);
+        Some(acc)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for RunningGcd<I> where
+    I: ExactSizeIterator,
+    I::Item: Gcd,
+{ }
+
+/// An iterator adaptor that yields the least common multiple of
+/// all elements seen so far.
+///
+/// See [*.running_lcm()*](trait.Itertools.html#method.running_lcm) for more information.
+#[derive(Clone)]
+pub struct RunningLcm<I> where
+    I: Iterator,
+{
+    iter: I,
+    acc: Option<I::Item>,
+}
+
+impl<I> RunningLcm<I> where
+    I: Iterator,
+{
+    /// Create a new `RunningLcm` iterator.
+    pub fn new(iter: I) -> Self
+    {
+        RunningLcm { iter: iter, acc: None }
+    }
+}
+
+impl<I> Iterator for RunningLcm<I> where
+    I: Iterator,
+    I::Item: Gcd,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item>
+    {
+        let elt = match self.iter.next() {
+            None => return None,
+            Some(x) => x,
+        };
+        let acc = match self.acc {
+            None => elt.lcm(elt),
+            Some(acc) => acc.lcm(elt),
+        };
+        self.acc = Some(acc);
+        Some(acc)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for RunningLcm<I> where
+    I: ExactSizeIterator,
+    I::Item: Gcd,
+{ }
+
+/// An iterator adaptor that marks each element with whether it is a
+/// local maximum.
+///
+/// Iterator element type is `(bool, I::Item)`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.local_maxima()*](trait.Itertools.html#method.local_maxima) for more information.
+#[derive(Clone)]
+pub struct LocalMaxima<I> where
+    I: Iterator,
+{
+    iter: Fuse<I>,
+    prev: Option<I::Item>,
+    cur: Option<I::Item>,
+}
+
+impl<I> LocalMaxima<I> where
+    I: Iterator,
+{
+    /// Create a new `LocalMaxima` iterator.
+    pub fn new(iter: I) -> Self
+    {
+        let mut iter = iter.fuse();
+        LocalMaxima { cur: iter.next(), iter: iter, prev: None }
+    }
+}
+
+impl<I> Iterator for LocalMaxima<I> where
+    I: Iterator,
+    I::Item: PartialOrd + Clone,
+{
+    type Item = (bool, I::Item);
+
+    fn next(&mut self) -> Option<(bool, I::Item)>
+    {
+        let cur = match self.cur.take() {
+            None => return None,
+            Some(x) => x,
+        };
+        let next = self.iter.next();
+        let is_peak = match (&self.prev, &next) {
+            (&Some(ref prev), &Some(ref next)) => cur > *prev && cur > *next,
+            _ => false,
+        };
+        self.prev = Some(cur.clone());
+        self.cur = next;
+        Some((is_peak, cur))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        size_hint::add_scalar(self.iter.size_hint(), self.cur.is_some() as usize)
+    }
+}
+
+impl<I> ExactSizeIterator for LocalMaxima<I> where
+    I: ExactSizeIterator,
+    I::Item: PartialOrd + Clone,
+{ }
+
+/// An iterator adaptor that merges the two base iterators in ascending
+/// order of a key.
+/// If both base iterators are sorted (ascending) by the key, the result is sorted.
+///
+/// Iterator element type is `I::Item`.
+///
+/// See [*.merge_by_key()*](trait.Itertools.html#method.merge_by_key) for more information.
+pub struct MergeByKey<I, J, F> where
+    I: Iterator,
+    J: Iterator<Item=I::Item>,
+{
+    merge: MergeCore<I, J>,
+    key: F,
+}
+
+/// Create a `MergeByKey` iterator.
+pub fn merge_by_key_new<I, J, F>(a: I, b: J, key: F) -> MergeByKey<I, J, F>
+    where I: Iterator,
+          J: Iterator<Item=I::Item>,
+{
+    MergeByKey {
+        merge: MergeCore {
+            a: a.peekable(),
+            b: b.peekable(),
+            fused: None,
+        },
+        key: key,
+    }
+}
+
+impl<I, J, F> Clone for MergeByKey<I, J, F> where
+    I: Iterator,
+    J: Iterator<Item=I::Item>,
+    Peekable<I>: Clone,
+    Peekable<J>: Clone,
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        clone_fields!(MergeByKey, self, merge, key)
+    }
+}
+
+impl<I, J, K, F> Iterator for MergeByKey<I, J, F> where
+    I: Iterator,
+    J: Iterator<Item=I::Item>,
+    K: Ord,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let key = &mut self.key;
+        self.merge.next_with(|a, b| key(a) <= key(b))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.merge.size_hint()
+    }
+}
+
+/// An iterator adaptor that pairs each element with the number of times
+/// its key has been seen so far.
+///
+/// See [*.running_counts()*](trait.Itertools.html#method.running_counts) for more information.
+#[derive(Clone)]
+pub struct RunningCounts<I, K, F> {
+    iter: I,
+    counts: HashMap<K, usize>,
+    key: F,
+}
+
+impl<I, K, F> RunningCounts<I, K, F> where
+    I: Iterator,
+    K: Eq + Hash,
+    F: FnMut(&I::Item) -> K,
+{
+    /// Create a new `RunningCounts` iterator.
+    pub fn new(iter: I, key: F) -> Self {
+        RunningCounts {
+            iter: iter,
+            counts: HashMap::new(),
+            key: key,
+        }
+    }
+}
+
+impl<I, K, F> Iterator for RunningCounts<I, K, F> where
+    I: Iterator,
+    K: Eq + Hash,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = (I::Item, usize);
+
+    fn next(&mut self) -> Option<(I::Item, usize)> {
+        match self.iter.next() {
+            None => None,
+            Some(elt) => {
+                let count = self.counts.entry((self.key)(&elt)).or_insert(0);
+                *count += 1;
+                Some((elt, *count))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, K, F> ExactSizeIterator for RunningCounts<I, K, F> where
+    I: ExactSizeIterator,
+    K: Eq + Hash,
+    F: FnMut(&I::Item) -> K,
+{ }
+
+/// An iterator adaptor that gathers elements into chunks of a fixed size,
+/// reversing every other chunk.
+///
+/// Iterator element type is `Vec<I::Item>`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.zigzag_chunks()*](trait.Itertools.html#method.zigzag_chunks) for more information.
+#[derive(Clone)]
+pub struct ZigzagChunks<I> {
+    iter: Fuse<I>,
+    size: usize,
+    reverse: bool,
+}
+
+impl<I> ZigzagChunks<I> where
+    I: Iterator,
+{
+    /// Create a new `ZigzagChunks` iterator.
+    ///
+    /// **Panics** if `size` is 0.
+    pub fn new(iter: I, size: usize) -> Self
+    {
+        assert!(size != 0);
+        ZigzagChunks { iter: iter.fuse(), size: size, reverse: false }
+    }
+}
+
+impl<I> Iterator for ZigzagChunks<I> where
+    I: Iterator,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>>
+    {
+        let mut chunk: Vec<_> = self.iter.by_ref().take(self.size).collect();
+        if chunk.is_empty() {
+            return None;
+        }
+        if self.reverse {
+            chunk.reverse();
+        }
+        self.reverse = !self.reverse;
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        let div = |x: usize| {
+            if x == 0 {
+                0
+            } else {
+                1 + (x - 1) / self.size
+            }
+        };
+        let (low, hi) = self.iter.size_hint();
+        (div(low), hi.map(div))
+    }
+}
+
+/// An iterator adaptor that yields the differences between consecutive
+/// elements of the base iterator.
+///
+/// Iterator element type is `<I::Item as Sub>::Output`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.gaps()*](trait.Itertools.html#method.gaps) for more information.
+#[derive(Clone)]
+pub struct Gaps<I> where
+    I: Iterator,
+{
+    iter: I,
+    last: Option<I::Item>,
+}
+
+impl<I> Gaps<I> where
+    I: Iterator,
+{
+    /// Create a new `Gaps` iterator.
+    pub fn new(mut iter: I) -> Self
+    {
+        Gaps { last: iter.next(), iter: iter }
+    }
+}
+
+impl<I> Iterator for Gaps<I> where
+    I: Iterator,
+    I::Item: Clone + Sub,
+{
+    type Item = <I::Item as Sub>::Output;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        let last = match self.last.take() {
+            None => return None,
+            Some(x) => x,
+        };
+        match self.iter.next() {
+            None => None,
+            Some(elt) => {
+                self.last = Some(elt.clone());
+                Some(elt - last)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        if self.last.is_some() {
+            self.iter.size_hint()
+        } else {
+            (0, Some(0))
+        }
+    }
+}
+
+impl<I> ExactSizeIterator for Gaps<I> where
+    I: ExactSizeIterator,
+    I::Item: Clone + Sub,
+{ }
+
+/// An iterator adaptor that yields each element followed by a number of
+/// values linearly interpolated towards the next element.
+///
+/// Iterator element type is `I::Item`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.interpolate()*](trait.Itertools.html#method.interpolate) for more information.
+#[derive(Clone)]
+pub struct Interpolate<I> where
+    I: Iterator,
+{
+    iter: Fuse<I>,
+    steps: usize,
+    a: Option<I::Item>,
+    b: Option<I::Item>,
+    index: usize,
+}
+
+impl<I> Interpolate<I> where
+    I: Iterator,
+{
+    /// Create a new `Interpolate` iterator.
+    pub fn new(iter: I, steps: usize) -> Self
+    {
+        let mut iter = iter.fuse();
+        Interpolate {
+            a: iter.next(),
+            b: iter.next(),
+            iter: iter,
+            steps: steps,
+            index: 0,
+        }
+    }
+}
+
+impl<F, I> Iterator for Interpolate<I> where
+    I: Iterator<Item=F>,
+    F: Copy + Add<Output=F> + Sub<Output=F> + Mul<Output=F> + Div<Output=F>,
+    usize: ToFloat<F>,
+{
+    type Item = F;
+
+    fn next(&mut self) -> Option<F>
+    {
+        loop {
+            let a = match self.a {
+                None => return None,
+                Some(a) => a,
+            };
+            let b = match self.b {
+                None => {
+                    self.a = None;
+                    return Some(a);
+                }
+                Some(b) => b,
+            };
+            if self.index <= self.steps {
+                let i = self.index;
+                self.index += 1;
+                if i == 0 {
+                    return Some(a);
+                }
+                let t = i.to_float() / (self.steps + 1).to_float();
+                return Some(a + (b - a) * t);
+            }
+            self.a = self.b;
+            self.b = self.iter.next();
+            self.index = 0;
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        if self.a.is_none() {
+            (0, Some(0))
+        } else if self.b.is_none() {
+            (1, Some(1))
+        } else {
+            // each remaining segment yields steps + 1 elements, plus the last element
+            let sh = size_hint::add_scalar(self.iter.size_hint(), 1);
+            let sh = size_hint::mul_scalar(sh, self.steps.saturating_add(1));
+            size_hint::sub_scalar(size_hint::add_scalar(sh, 1), self.index)
+        }
+    }
+}
+
+/// An iterator adaptor that gathers the elements between sentinel
+/// elements into vectors.
+///
+/// Iterator element type is `Vec<I::Item>`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.frames()*](trait.Itertools.html#method.frames) for more information.
+#[derive(Clone)]
+pub struct Frames<I, F> {
+    iter: Fuse<I>,
+    is_sentinel: F,
+}
+
+impl<I, F> Frames<I, F> where
+    I: Iterator,
+{
+    /// Create a new `Frames` iterator.
+    pub fn new(iter: I, is_sentinel: F) -> Self
+    {
+        Frames { iter: iter.fuse(), is_sentinel: is_sentinel }
+    }
+}
+
+impl<I, F> Iterator for Frames<I, F> where
+    I: Iterator,
+    F: FnMut(&I::Item) -> bool,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>>
+    {
+        let mut frame = Vec::new();
+        for elt in self.iter.by_ref() {
+            if (self.is_sentinel)(&elt) {
+                return Some(frame);
+            }
+            frame.push(elt);
+        }
+        if frame.is_empty() {
+            None
+        } else {
+            Some(frame)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        let (low, hi) = self.iter.size_hint();
+        ((low > 0) as usize, hi)
+    }
+}
+
+/// An iterator adaptor that replaces each element failing a predicate
+/// with a fill value.
+///
+/// Iterator element type is `I::Item`.
+///
+/// See [*.fill_failing()*](trait.Itertools.html#method.fill_failing) for more information.
+#[derive(Clone)]
+pub struct FillFailing<I, F> where
+    I: Iterator,
+{
+    iter: I,
+    pred: F,
+    fill: I::Item,
+}
+
+impl<I, F> FillFailing<I, F> where
+    I: Iterator,
+{
+    /// Create a new `FillFailing` iterator.
+    pub fn new(iter: I, pred: F, fill: I::Item) -> Self
+    {
+        FillFailing { iter: iter, pred: pred, fill: fill }
+    }
+}
+
+impl<I, F> Iterator for FillFailing<I, F> where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&I::Item) -> bool,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item>
+    {
+        match self.iter.next() {
+            None => None,
+            Some(elt) => if (self.pred)(&elt) {
+                Some(elt)
+            } else {
+                Some(self.fill.clone())
+            },
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, F> ExactSizeIterator for FillFailing<I, F> where
+    I: ExactSizeIterator,
+    I::Item: Clone,
+    F: FnMut(&I::Item) -> bool,
+{ }
+
+/// An iterator adaptor that marks the first element of each run of
+/// elements that map to the same key.
+///
+/// Iterator element type is `(bool, I::Item)`.
+///
+/// See [*.flatten_marked()*](trait.Itertools.html#method.flatten_marked) for more information.
+#[derive(Clone)]
+pub struct FlattenMarked<I, K, F> {
+    iter: I,
+    key: F,
+    current_key: Option<K>,
+}
+
+impl<I, K, F> FlattenMarked<I, K, F> {
+    /// Create a new `FlattenMarked` iterator.
+    pub fn new(iter: I, key: F) -> Self
+    {
+        FlattenMarked { iter: iter, key: key, current_key: None }
+    }
+}
+
+impl<I, K, F> Iterator for FlattenMarked<I, K, F> where
+    I: Iterator,
+    K: PartialEq,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = (bool, I::Item);
+
+    fn next(&mut self) -> Option<(bool, I::Item)>
+    {
+        match self.iter.next() {
+            None => None,
+            Some(elt) => {
+                let key = (self.key)(&elt);
+                let is_start = match self.current_key {
+                    Some(ref old_key) => *old_key != key,
+                    None => true,
+                };
+                self.current_key = Some(key);
+                Some((is_start, elt))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, K, F> ExactSizeIterator for FlattenMarked<I, K, F> where
+    I: ExactSizeIterator,
+    K: PartialEq,
+    F: FnMut(&I::Item) -> K,
+{ }
+
+/// An iterator adaptor that yields each pair of adjacent elements,
+/// including the pair that wraps around from the last element to the first.
+///
+/// Iterator element type is `(I::Item, I::Item)`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.circular_pairs()*](trait.Itertools.html#method.circular_pairs) for more information.
+#[derive(Clone)]
+pub struct CircularPairs<I> where
+    I: Iterator,
+{
+    iter: Fuse<I>,
+    first: Option<I::Item>,
+    last: Option<I::Item>,
+}
+
+impl<I> CircularPairs<I> where
+    I: Iterator,
+    I::Item: Clone,
+{
+    /// Create a new `CircularPairs` iterator.
+    pub fn new(iter: I) -> Self
+    {
+        let mut iter = iter.fuse();
+        let first = iter.next();
+        CircularPairs { iter: iter, last: first.clone(), first: first }
+    }
+}
+
+impl<I> Iterator for CircularPairs<I> where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = (I::Item, I::Item);
+
+    fn next(&mut self) -> Option<(I::Item, I::Item)>
+    {
+        let last = match self.last.take() {
+            None => return None,
+            Some(x) => x,
+        };
+        match self.iter.next() {
+            Some(elt) => {
+                self.last = Some(elt.clone());
+                Some((last, elt))
+            }
+            None => self.first.take().map(|first| (last, first)),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        if self.last.is_some() {
+            size_hint::add_scalar(self.iter.size_hint(), 1)
+        } else {
+            (0, Some(0))
+        }
+    }
+}
+
+impl<I> ExactSizeIterator for CircularPairs<I> where
+    I: ExactSizeIterator,
+    I::Item: Clone,
+{ }
+
+/// An iterator adaptor that yields elements at exponentially growing
+/// distances from each other.
+///
+/// Iterator element type is `I::Item`.
+///
+/// See [*.sample_exponential()*](trait.Itertools.html#method.sample_exponential)
+/// for more information.
+#[derive(Clone)]
+pub struct SampleExponential<I> {
+    iter: I,
+    base: usize,
+    gap: usize,
+    started: bool,
+}
+
+impl<I> SampleExponential<I> where
+    I: Iterator,
+{
+    /// Create a new `SampleExponential` iterator.
+    ///
+    /// **Panics** if `base` is 0.
+    pub fn new(iter: I, base: usize) -> Self
+    {
+        assert!(base != 0);
+        SampleExponential { iter: iter, base: base, gap: 1, started: false }
+    }
+}
+
+impl<I> Iterator for SampleExponential<I> where
+    I: Iterator,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item>
+    {
+        if !self.started {
+            self.started = true;
+            self.iter.next()
+        } else {
+            let elt = self.iter.nth(self.gap - 1);
+            self.gap = self.gap.saturating_mul(self.base);
+            elt
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        let count = |mut n: usize| {
+            let mut count = 0;
+            if !self.started {
+                if n == 0 {
+                    return 0;
+                }
+                count += 1;
+                n -= 1;
+            }
+            if self.base == 1 {
+                return count + n;
+            }
+            let mut gap = self.gap;
+            while n >= gap {
+                n -= gap;
+                count += 1;
+                gap = gap.saturating_mul(self.base);
+            }
+            count
+        };
+        let (low, hi) = self.iter.size_hint();
+        (count(low), hi.map(count))
+    }
+}
+
+/// An iterator adaptor that combines each run of consecutive elements
+/// that map to the same key into one element.
+///
+/// See [*.merge_runs()*](trait.Itertools.html#method.merge_runs) for more information.
+pub struct MergeRuns<I, K, F, G>
+    where I: Iterator,
+{
+    iter: I,
+    last: Option<(K, I::Item)>,
+    key: F,
+    combine: G,
+}
+
+impl<I, K, F, G> Clone for MergeRuns<I, K, F, G>
+    where I: Iterator + Clone,
+          I::Item: Clone,
+          K: Clone,
+          F: Clone,
+          G: Clone,
+{
+    fn clone(&self) -> Self {
+        clone_fields!(MergeRuns, self, iter, last, key, combine)
+    }
+}
+
+impl<I, K, F, G> MergeRuns<I, K, F, G> where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+{
+    /// Create a new `MergeRuns`.
+    pub fn new(mut iter: I, mut key: F, combine: G) -> Self {
+        let last = iter.next().map(|elt| (key(&elt), elt));
+        MergeRuns {
+            iter: iter,
+            last: last,
+            key: key,
+            combine: combine,
+        }
+    }
+}
+
+impl<I, K, F, G> Iterator for MergeRuns<I, K, F, G>
+    where I: Iterator,
+          K: PartialEq,
+          F: FnMut(&I::Item) -> K,
+          G: FnMut(I::Item, I::Item) -> I::Item,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        // this fuses the iterator
+        let (run_key, mut acc) = match self.last.take() {
+            None => return None,
+            Some(x) => x,
+        };
+        for elt in &mut self.iter {
+            let key = (self.key)(&elt);
+            if key == run_key {
+                acc = (self.combine)(acc, elt);
+            } else {
+                self.last = Some((key, elt));
+                return Some(acc);
+            }
+        }
+        Some(acc)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, hi) = size_hint::add_scalar(self.iter.size_hint(),
+                                              self.last.is_some() as usize);
+        ((low > 0) as usize, hi)
+    }
+}
+
+/// An iterator adaptor that pairs each element with the element `k`
+/// places after it.
+///
+/// Iterator element type is `(I::Item, I::Item)`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.zip_shift()*](trait.Itertools.html#method.zip_shift) for more information.
+#[derive(Clone)]
+pub struct ZipShift<I> where
+    I: Iterator,
+{
+    iter: Fuse<I>,
+    k: usize,
+    buf: VecDeque<I::Item>,
+}
+
+impl<I> ZipShift<I> where
+    I: Iterator,
+{
+    /// Create a new `ZipShift` iterator.
+    pub fn new(iter: I, k: usize) -> Self
+    {
+        ZipShift { iter: iter.fuse(), k: k, buf: VecDeque::new() }
+    }
+}
+
+impl<I> Iterator for ZipShift<I> where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = (I::Item, I::Item);
+
+    fn next(&mut self) -> Option<(I::Item, I::Item)>
+    {
+        while let Some(elt) = self.iter.next() {
+            if self.buf.len() == self.k {
+                self.buf.push_back(elt.clone());
+                let first = self.buf.pop_front().unwrap();
+                return Some((first, elt));
+            }
+            self.buf.push_back(elt);
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        let sh = size_hint::add_scalar(self.iter.size_hint(), self.buf.len());
+        size_hint::sub_scalar(sh, self.k)
+    }
+}
+
+impl<I> ExactSizeIterator for ZipShift<I> where
+    I: ExactSizeIterator,
+    I::Item: Clone,
+{ }
+
+/// An iterator adaptor that collects elements into batches, ending each
+/// batch when a predicate on its contents holds.
+///
+/// Iterator element type is `Vec<I::Item>`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.batch_until()*](trait.Itertools.html#method.batch_until) for more information.
+#[derive(Clone)]
+pub struct BatchUntil<I, F> {
+    iter: Fuse<I>,
+    f: F,
+}
+
+impl<I, F> BatchUntil<I, F> where
+    I: Iterator,
+{
+    /// Create a new `BatchUntil` iterator.
+    pub fn new(iter: I, f: F) -> Self
+    {
+        BatchUntil { iter: iter.fuse(), f: f }
+    }
+}
+
+impl<I, F> Iterator for BatchUntil<I, F> where
+    I: Iterator,
+    F: FnMut(&[I::Item]) -> bool,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>>
+    {
+        let mut batch = Vec::new();
+        for elt in self.iter.by_ref() {
+            batch.push(elt);
+            if (self.f)(&batch) {
+                return Some(batch);
+            }
+        }
+        if batch.is_empty() {
+            None
+        } else {
+            Some(batch)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        let (low, hi) = self.iter.size_hint();
+        (cmp::min(low, 1), hi)
+    }
+}
+
+/// An iterator adaptor that flattens groups of values, pairing each value
+/// with a clone of its group's key.
+///
+/// Iterator element type is `(K, V)`.
+///
+/// See [*.ungroup()*](trait.Itertools.html#method.ungroup) for more information.
+#[derive(Clone)]
+pub struct Ungroup<I, K, V> {
+    iter: I,
+    current: Option<(K, vec::IntoIter<V>)>,
+}
+
+impl<I, K, V> Ungroup<I, K, V> {
+    /// Create a new `Ungroup` iterator.
+    pub fn new(iter: I) -> Self
+    {
+        Ungroup { iter: iter, current: None }
+    }
+}
+
+impl<I, K, V> Iterator for Ungroup<I, K, V> where
+    I: Iterator<Item=(K, Vec<V>)>,
+    K: Clone,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)>
+    {
+        loop {
+            if let Some((ref key, ref mut values)) = self.current {
+                if let Some(v) = values.next() {
+                    return Some((key.clone(), v));
+                }
+            }
+            match self.iter.next() {
+                None => return None,
+                Some((key, values)) => self.current = Some((key, values.into_iter())),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        // groups may be of any size, so only the current group is known
+        let low = match self.current {
+            Some((_, ref values)) => values.len(),
+            None => 0,
+        };
+        match self.iter.size_hint() {
+            (_, Some(0)) => (low, Some(low)),
+            _ => (low, None),
+        }
+    }
+}
+
+/// An iterator adaptor that yields the running total of a sequence of
+/// counts.
+///
+/// Iterator element type is `usize`.
+///
+/// See [*.cumulative()*](trait.Itertools.html#method.cumulative) for more information.
+#[derive(Clone)]
+pub struct Cumulative<I> {
+    iter: I,
+    total: usize,
+}
+
+impl<I> Cumulative<I> {
+    /// Create a new `Cumulative` iterator.
+    pub fn new(iter: I) -> Self
+    {
+        Cumulative { iter: iter, total: 0 }
+    }
+}
+
+impl<I> Iterator for Cumulative<I> where
+    I: Iterator<Item=usize>,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize>
+    {
+        self.iter.next().map(|count| {
+            self.total = self.total.checked_add(count)
+                                   .expect("Cumulative: running total overflowed usize");
+            self.total
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for Cumulative<I> where
+    I: ExactSizeIterator<Item=usize>,
+{ }
+
+/// An iterator adaptor that yields each distinct pair of adjacent elements,
+/// in the order they are first seen.
+///
+/// Iterator element type is `(I::Item, I::Item)`.
+///
+/// See [*.transitions()*](trait.Itertools.html#method.transitions) for more information.
+#[derive(Clone)]
+pub struct Transitions<I> where
+    I: Iterator,
+{
+    iter: I,
+    last: Option<I::Item>,
+    seen: HashSet<(I::Item, I::Item)>,
+}
+
+impl<I> Transitions<I> where
+    I: Iterator,
+    I::Item: Eq + Hash,
+{
+    /// Create a new `Transitions` iterator.
+    pub fn new(iter: I) -> Self
+    {
+        Transitions { iter: iter, last: None, seen: HashSet::new() }
+    }
+}
+
+impl<I> Iterator for Transitions<I> where
+    I: Iterator,
+    I::Item: Clone + Eq + Hash,
+{
+    type Item = (I::Item, I::Item);
+
+    fn next(&mut self) -> Option<(I::Item, I::Item)>
+    {
+        if self.last.is_none() {
+            self.last = self.iter.next();
+        }
+        while let Some(elt) = self.iter.next() {
+            let from = mem::replace(&mut self.last, Some(elt.clone())).unwrap();
+            let pair = (from, elt);
+            if !self.seen.contains(&pair) {
+                self.seen.insert(pair.clone());
+                return Some(pair);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        // every remaining element may repeat an already seen transition,
+        // except that the first transition is always new
+        let sh = self.iter.size_hint();
+        let (low, hi) = if self.last.is_some() { sh } else { size_hint::sub_scalar(sh, 1) };
+        (if self.seen.is_empty() { cmp::min(low, 1) } else { 0 }, hi)
+    }
+}
+
+/// An iterator adaptor that yields the key and index range of each run of
+/// elements that map to the same key.
+///
+/// Iterator element type is `(K, usize, usize)`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.run_spans()*](trait.Itertools.html#method.run_spans) for more information.
+#[derive(Clone)]
+pub struct RunSpans<I, K, F> {
+    iter: Fuse<I>,
+    key: F,
+    /// The key and start index of the current run
+    current: Option<(K, usize)>,
+    pos: usize,
+}
+
+impl<I, K, F> RunSpans<I, K, F> where
+    I: Iterator,
+{
+    /// Create a new `RunSpans` iterator.
+    pub fn new(iter: I, key: F) -> Self
+    {
+        RunSpans { iter: iter.fuse(), key: key, current: None, pos: 0 }
+    }
+}
+
+impl<I, K, F> Iterator for RunSpans<I, K, F> where
+    I: Iterator,
+    K: PartialEq,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = (K, usize, usize);
+
+    fn next(&mut self) -> Option<(K, usize, usize)>
+    {
+        let key = &mut self.key;
+        while let Some(elt) = self.iter.next() {
+            let k = key(&elt);
+            let start = self.pos;
+            self.pos += 1;
+            if let Some((ref run_key, _)) = self.current {
+                if *run_key == k {
+                    continue;
+                }
+            }
+            if let Some((run_key, run_start)) = mem::replace(&mut self.current, Some((k, start))) {
+                return Some((run_key, run_start, start));
+            }
+        }
+        self.current.take().map(|(k, start)| (k, start, self.pos))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        let (low, hi) = size_hint::add_scalar(self.iter.size_hint(),
+                                              self.current.is_some() as usize);
+        (cmp::min(low, 1), hi)
+    }
+}
+
+/// Pull elements from `iter` into `buf` until it holds index `index`;
+/// return whether it does.
+fn fill_to<I>(iter: &mut Fuse<I>, buf: &mut Vec<I::Item>, index: usize) -> bool
+    where I: Iterator
+{
+    while buf.len() <= index {
+        match iter.next() {
+            Some(elt) => buf.push(elt),
+            None => return false,
+        }
+    }
+    true
+}
+
+/// An iterator adaptor that iterates over the cartesian product of
+/// two iterators along anti-diagonals.
+///
+/// Iterator element type is `(I::Item, J::Item)`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.product_diagonal()*](trait.Itertools.html#method.product_diagonal) for more information.
+#[derive(Clone)]
+pub struct ProductDiagonal<I, J> where
+    I: Iterator,
+    J: Iterator,
+{
+    a: Fuse<I>,
+    b: Fuse<J>,
+    a_buf: Vec<I::Item>,
+    b_buf: Vec<J::Item>,
+    /// Index of the current anti-diagonal, where `i + j == diag`
+    diag: usize,
+    i: usize,
+    a_done: bool,
+    b_done: bool,
+    count: usize,
+}
+
+impl<I, J> ProductDiagonal<I, J> where
+    I: Iterator,
+    J: Iterator,
+{
+    /// Create a new `ProductDiagonal` iterator.
+    pub fn new(a: I, b: J) -> Self
+    {
+        ProductDiagonal {
+            a: a.fuse(),
+            b: b.fuse(),
+            a_buf: Vec::new(),
+            b_buf: Vec::new(),
+            diag: 0,
+            i: 0,
+            a_done: false,
+            b_done: false,
+            count: 0,
+        }
+    }
+}
+
+impl<I, J> Iterator for ProductDiagonal<I, J> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<(I::Item, J::Item)>
+    {
+        loop {
+            if self.i > self.diag {
+                self.diag += 1;
+                self.i = 0;
+            }
+            let (a_len, b_len) = (self.a_buf.len(), self.b_buf.len());
+            if (self.a_done && a_len == 0) || (self.b_done && b_len == 0) ||
+                (self.a_done && self.b_done && self.diag + 2 > a_len + b_len) {
+                return None;
+            }
+            let has_a = fill_to(&mut self.a, &mut self.a_buf, self.i);
+            let has_b = fill_to(&mut self.b, &mut self.b_buf, self.diag - self.i);
+            self.a_done |= !has_a;
+            self.b_done |= !has_b;
+            if has_a && has_b {
+                let i = self.i;
+                self.i += 1;
+                self.count += 1;
+                return Some((self.a_buf[i].clone(), self.b_buf[self.diag - i].clone()));
+            } else if !has_a {
+                // the rest of this diagonal is past the end of `a`
+                self.i = self.diag + 1;
+            } else if !self.b_buf.is_empty() {
+                // skip ahead to the first index where `b` is long enough
+                self.i = self.diag + 1 - self.b_buf.len();
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        let a = size_hint::add_scalar(self.a.size_hint(), self.a_buf.len());
+        let b = size_hint::add_scalar(self.b.size_hint(), self.b_buf.len());
+        size_hint::sub_scalar(size_hint::mul(a, b), self.count)
+    }
+}
+
+/// An iterator adaptor that yields the elements of the base iterator, and
+/// then the same elements in reverse, excluding the last one.
+///
+/// Iterator element type is `I::Item`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.palindrome()*](trait.Itertools.html#method.palindrome) for more information.
+#[derive(Clone)]
+pub struct Palindrome<I> where
+    I: Iterator,
+{
+    iter: Fuse<I>,
+    buf: Vec<I::Item>,
+    forward: bool,
+}
+
+impl<I> Palindrome<I> where
+    I: Iterator,
+{
+    /// Create a new `Palindrome` iterator.
+    pub fn new(iter: I) -> Self
+    {
+        let (low, _) = iter.size_hint();
+        Palindrome {
+            iter: iter.fuse(),
+            buf: Vec::with_capacity(low),
+            forward: true,
+        }
+    }
+}
+
+impl<I> Iterator for Palindrome<I> where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item>
+    {
+        if self.forward {
+            if let Some(elt) = self.iter.next() {
+                self.buf.push(elt.clone());
+                return Some(elt);
+            }
+            // the middle element is not repeated
+            self.forward = false;
+            self.buf.pop();
+        }
+        self.buf.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        if self.forward {
+            let (low, hi) = size_hint::add_scalar(self.iter.size_hint(), self.buf.len());
+            let back = (low.saturating_sub(1), hi.map(|hi| hi.saturating_sub(1)));
+            size_hint::add(self.iter.size_hint(), back)
+        } else {
+            (self.buf.len(), Some(self.buf.len()))
+        }
+    }
+}
+
+impl<I> ExactSizeIterator for Palindrome<I> where
+    I: ExactSizeIterator,
+    I::Item: Clone,
+{ }
+
+/// An iterator adaptor that gathers the elements of the base iterator into
+/// vectors of a fixed size; the last vector may be shorter.
+///
+/// Iterator element type is `Vec<I::Item>`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.chunks()*](trait.Itertools.html#method.chunks) for more information.
+#[derive(Clone)]
+pub struct VecChunks<I> {
+    iter: Fuse<I>,
+    size: usize,
+}
+
+impl<I> VecChunks<I> where
+    I: Iterator,
+{
+    /// Create a new `VecChunks` iterator.
+    ///
+    /// **Panics** if `size` is 0.
+    pub fn new(iter: I, size: usize) -> Self
+    {
+        assert!(size != 0);
+        VecChunks { iter: iter.fuse(), size: size }
+    }
+}
+
+impl<I> Iterator for VecChunks<I> where
+    I: Iterator,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>>
+    {
+        let chunk = self.iter.by_ref().take(self.size).collect::<Vec<_>>();
+        if chunk.is_empty() {
+            None
+        } else {
+            Some(chunk)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        let size = self.size;
+        let div_ceil = |n: usize| n / size + (n % size != 0) as usize;
+        let (low, hi) = self.iter.size_hint();
+        (div_ceil(low), hi.map(div_ceil))
+    }
+}
+
+/// An iterator adaptor that yields each window of adjacent elements of
+/// the base iterator as a tuple.
+///
+/// Iterator element type is `T`, a tuple like `(I::Item, I::Item)`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.tuple_windows()*](trait.Itertools.html#method.tuple_windows) for more information.
+#[derive(Clone)]
+pub struct TupleWindows<I, T> {
+    iter: Fuse<I>,
+    last: Option<T>,
+}
+
+impl<I, T> TupleWindows<I, T> where
+    I: Iterator,
+{
+    /// Create a new `TupleWindows` iterator.
+    pub fn new(iter: I) -> Self
+    {
+        TupleWindows { iter: iter.fuse(), last: None }
+    }
+}
+
+impl<I, T> Iterator for TupleWindows<I, T> where
+    I: Iterator<Item=T::Item>,
+    T: TupleCollect + Clone,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T>
+    {
+        match self.last {
+            None => {
+                self.last = T::collect_from_iter(&mut self.iter);
+                self.last.clone()
+            }
+            Some(ref mut last) => match self.iter.next() {
+                None => None,
+                Some(elt) => {
+                    last.left_shift_push(elt);
+                    Some(last.clone())
+                }
+            },
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        match self.last {
+            None => size_hint::sub_scalar(self.iter.size_hint(), T::num_items() - 1),
+            Some(_) => self.iter.size_hint(),
+        }
+    }
+}
+
+/// An iterator adaptor that yields elements while a predicate holds for
+/// an accumulator folded over them.
+///
+/// Iterator element type is `I::Item`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.take_while_fold()*](trait.Itertools.html#method.take_while_fold) for more information.
+#[derive(Clone)]
+pub struct TakeWhileFold<I, B, F, G> {
+    iter: I,
+    /// The accumulator; `None` when done
+    acc: Option<B>,
+    f: F,
+    pred: G,
+}
+
+impl<I, B, F, G> TakeWhileFold<I, B, F, G> {
+    /// Create a new `TakeWhileFold` iterator.
+    pub fn new(iter: I, init: B, f: F, pred: G) -> Self
+    {
+        TakeWhileFold { iter: iter, acc: Some(init), f: f, pred: pred }
+    }
+}
+
+impl<I, B, F, G> Iterator for TakeWhileFold<I, B, F, G> where
+    I: Iterator,
+    F: FnMut(B, &I::Item) -> B,
+    G: FnMut(&B) -> bool,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item>
+    {
+        let acc = match self.acc.take() {
+            None => return None,
+            Some(acc) => acc,
+        };
+        match self.iter.next() {
+            None => None,
+            Some(elt) => {
+                let acc = (self.f)(acc, &elt);
+                if (self.pred)(&acc) {
+                    self.acc = Some(acc);
+                    Some(elt)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        if self.acc.is_some() {
+            (0, self.iter.size_hint().1)
+        } else {
+            (0, Some(0))
+        }
+    }
+}
+
+/// An iterator that yields all subsets of the elements of the base
+/// iterator in Gray code order, as vectors.
+///
+/// Iterator element type is `Vec<I::Item>`.
+///
+/// See [*.powerset_gray()*](trait.Itertools.html#method.powerset_gray) for more information.
+#[derive(Clone)]
+pub struct PowersetGray<I> where
+    I: Iterator,
+{
+    elements: Vec<I::Item>,
+    /// Which elements are in the current subset
+    members: Vec<bool>,
+    /// Number of subsets yielded so far
+    count: usize,
+}
+
+impl<I> PowersetGray<I> where
+    I: Iterator,
+{
+    /// Create a new `PowersetGray` iterator.
+    pub fn new(iter: I) -> Self
+    {
+        let elements = iter.collect::<Vec<_>>();
+        PowersetGray {
+            members: vec![false; elements.len()],
+            elements: elements,
+            count: 0,
+        }
+    }
+
+    /// Total number of subsets, or `None` if it overflows `usize`.
+    fn total(&self) -> Option<usize>
+    {
+        let n = self.elements.len();
+        if n < mem::size_of::<usize>() * 8 {
+            Some(1 << n)
+        } else {
+            None
+        }
+    }
+}
+
+impl<I> Iterator for PowersetGray<I> where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>>
+    {
+        if Some(self.count) == self.total() {
+            return None;
+        }
+        if self.count > 0 {
+            // step k of a gray code toggles the bit of the lowest set bit of k
+            let index = self.count.trailing_zeros() as usize;
+            self.members[index] = !self.members[index];
+        }
+        self.count += 1;
+        Some(self.elements.iter().zip(&self.members)
+                 .filter(|&(_, &member)| member)
+                 .map(|(elt, _)| elt.clone())
+                 .collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        match self.total() {
+            Some(total) => (total - self.count, Some(total - self.count)),
+            None => (usize::max_value(), None),
+        }
+    }
+}
+
+/// An iterator adaptor that pairs each element with a value generated by
+/// a closure.
+///
+/// Iterator element type is `(I::Item, B)`.
+///
+/// See [*.zip_with()*](trait.Itertools.html#method.zip_with) for more information.
+#[derive(Clone)]
+pub struct ZipWith<I, F> {
+    iter: I,
+    f: F,
+}
+
+impl<I, F> ZipWith<I, F> {
+    /// Create a new `ZipWith` iterator.
+    pub fn new(iter: I, f: F) -> Self
+    {
+        ZipWith { iter: iter, f: f }
+    }
+}
+
+impl<B, I, F> Iterator for ZipWith<I, F> where
+    I: Iterator,
+    F: FnMut() -> B,
+{
+    type Item = (I::Item, B);
+
+    #[inline]
+    fn next(&mut self) -> Option<(I::Item, B)>
+    {
+        self.iter.next().map(|elt| (elt, (self.f)()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        self.iter.size_hint()
+    }
+}
+
+impl<B, I, F> ExactSizeIterator for ZipWith<I, F> where
+    I: ExactSizeIterator,
+    F: FnMut() -> B,
+{ }
+
+/// An iterator adaptor that repeats each element of the base iterator the
+/// number of times given by the paired count.
+///
+/// Iterator element type is `I::Item`.
+///
+/// See [*.repeat_zip()*](trait.Itertools.html#method.repeat_zip) for more information.
+#[derive(Clone)]
+pub struct RepeatZip<I, J> where
+    I: Iterator,
+{
+    iter: I,
+    counts: J,
+    current: Option<(I::Item, usize)>,
+}
+
+impl<I, J> RepeatZip<I, J> where
+    I: Iterator,
+{
+    /// Create a new `RepeatZip` iterator.
+    pub fn new(iter: I, counts: J) -> Self
+    {
+        RepeatZip { iter: iter, counts: counts, current: None }
+    }
+}
+
+impl<I, J> Iterator for RepeatZip<I, J> where
+    I: Iterator,
+    I::Item: Clone,
+    J: Iterator<Item=usize>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item>
+    {
+        loop {
+            match self.current.take() {
+                Some((elt, 0)) => drop(elt),
+                Some((elt, 1)) => return Some(elt),
+                Some((elt, n)) => {
+                    self.current = Some((elt.clone(), n - 1));
+                    return Some(elt);
+                }
+                None => {}
+            }
+            match (self.iter.next(), self.counts.next()) {
+                (Some(elt), Some(n)) => self.current = Some((elt, n)),
+                _ => return None,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        let pending = match self.current {
+            Some((_, n)) => n,
+            None => 0,
+        };
+        // the counts of elements yet to be paired are unknown
+        match size_hint::min(self.iter.size_hint(), self.counts.size_hint()) {
+            (_, Some(0)) => (pending, Some(pending)),
+            _ => (pending, None),
+        }
+    }
+}
+
+/// An iterator adaptor that applies a function to each pair of consecutive
+/// elements of the base iterator.
+///
+/// Iterator element type is `B`, the return type of the function.
+///
+/// This iterator is *fused*.
+///
+/// See [*.pairwise()*](trait.Itertools.html#method.pairwise) for more information.
+#[derive(Clone)]
+pub struct Pairwise<I, F> where
+    I: Iterator,
+{
+    iter: Fuse<I>,
+    prev: Option<I::Item>,
+    f: F,
+}
+
+impl<I, F> Pairwise<I, F> where
+    I: Iterator,
+{
+    /// Create a new `Pairwise` iterator.
+    pub fn new(iter: I, f: F) -> Self
+    {
+        Pairwise { iter: iter.fuse(), prev: None, f: f }
+    }
+}
+
+impl<B, I, F> Iterator for Pairwise<I, F> where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(I::Item, I::Item) -> B,
+{
+    type Item = B;
+
+    fn next(&mut self) -> Option<B>
+    {
+        let prev = match self.prev.take() {
+            Some(prev) => prev,
+            None => match self.iter.next() {
+                Some(first) => first,
+                None => return None,
+            },
+        };
+        self.iter.next().map(|elt| {
+            self.prev = Some(elt.clone());
+            (self.f)(prev, elt)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        match self.prev {
+            Some(_) => self.iter.size_hint(),
+            None => size_hint::sub_scalar(self.iter.size_hint(), 1),
+        }
+    }
+}
+
+impl<B, I, F> ExactSizeIterator for Pairwise<I, F> where
+    I: ExactSizeIterator,
+    I::Item: Clone,
+    F: FnMut(I::Item, I::Item) -> B,
+{ }
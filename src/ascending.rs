@@ -0,0 +1,87 @@
+use std::cmp;
+use std::error::Error;
+use std::fmt;
+
+/// An error from [*.ascending_by_key()*](trait.Itertools.html#method.ascending_by_key):
+/// an element's key was not greater than the key before it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OutOfOrder;
+
+impl fmt::Display for OutOfOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "key out of order")
+    }
+}
+
+impl Error for OutOfOrder {
+    fn description(&self) -> &str {
+        "key out of order"
+    }
+}
+
+/// An iterator adaptor that checks that the keys of the elements are
+/// strictly increasing.
+///
+/// Iterator element type is `Result<I::Item, OutOfOrder>`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.ascending_by_key()*](trait.Itertools.html#method.ascending_by_key) for more information.
+#[derive(Clone)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct AscendingByKey<I, K, F> {
+    iter: I,
+    key: F,
+    last: Option<K>,
+    done: bool,
+}
+
+impl<I, K, F> AscendingByKey<I, K, F> {
+    /// Create a new `AscendingByKey` iterator.
+    pub fn new(iter: I, key: F) -> Self
+    {
+        AscendingByKey { iter: iter, key: key, last: None, done: false }
+    }
+}
+
+impl<I, K, F> Iterator for AscendingByKey<I, K, F> where
+    I: Iterator,
+    K: Ord,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = Result<I::Item, OutOfOrder>;
+
+    fn next(&mut self) -> Option<Result<I::Item, OutOfOrder>>
+    {
+        if self.done {
+            return None;
+        }
+        let elt = match self.iter.next() {
+            Some(elt) => elt,
+            None => {
+                self.done = true;
+                return None;
+            }
+        };
+        let k = (self.key)(&elt);
+        if let Some(ref last) = self.last {
+            if k <= *last {
+                self.done = true;
+                return Some(Err(OutOfOrder));
+            }
+        }
+        self.last = Some(k);
+        Some(Ok(elt))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        if self.done {
+            (0, Some(0))
+        } else {
+            // an error ends the iteration early
+            let (low, hi) = self.iter.size_hint();
+            (cmp::min(low, 1), hi)
+        }
+    }
+}
@@ -279,6 +279,10 @@ impl<K, I, F> GroupInner<K, I, F>
 /// value. It should be stored in a local variable or temporary and
 /// iterated.
 ///
+/// The groups share the state of the `GroupByLazy` through a `RefCell`.
+/// **Panics** if the key function advances the same `GroupByLazy` or one
+/// of its groups, since that state is already borrowed.
+///
 /// See [`.group_by_lazy()`](trait.Itertools.html#method.group_by_lazy) for more information.
 pub struct GroupByLazy<K, I, F>
     where I: Iterator,
@@ -16,6 +16,8 @@ pub struct Intersperse<I> where
     element: I::Item,
     iter: Fuse<I>,
     peek: Option<I::Item>,
+    /// whether a separator is due before the last element taken from the back
+    back_sep: bool,
 }
 
 impl<I> Intersperse<I> where
@@ -25,7 +27,7 @@ impl<I> Intersperse<I> where
     pub fn new(iter: I, elt: I::Item) -> Self
     {
         let mut iter = iter.fuse();
-        Intersperse{peek: iter.next(), iter: iter, element: elt}
+        Intersperse{peek: iter.next(), iter: iter, element: elt, back_sep: false}
     }
 }
 
@@ -43,6 +45,154 @@ impl<I> Iterator for Intersperse<I> where
             self.peek = self.iter.next();
             if self.peek.is_some() {
                 Some(self.element.clone())
+            } else if self.back_sep {
+                // the separator before the elements taken from the back
+                self.back_sep = false;
+                Some(self.element.clone())
+            } else {
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        // 2 * SH + { 1 or 0 } + { 1 or 0 }
+        let has_peek = self.peek.is_some() as usize;
+        let sh = self.iter.size_hint();
+        size_hint::add_scalar(
+            size_hint::add(sh, sh), has_peek + self.back_sep as usize)
+    }
+}
+
+impl<I> DoubleEndedIterator for Intersperse<I> where
+    I: DoubleEndedIterator,
+    I::Item: Clone,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<I::Item>
+    {
+        if self.back_sep {
+            self.back_sep = false;
+            Some(self.element.clone())
+        } else {
+            match self.iter.next_back() {
+                Some(elt) => {
+                    self.back_sep = true;
+                    Some(elt)
+                }
+                None => self.peek.take(),
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+/// An iterator adaptor to insert separators, taken in turn from a cycle,
+/// between each element of the adapted iterator.
+///
+/// Iterator element type is `I::Item`
+///
+/// This iterator is *fused*.
+///
+/// See [*.intersperse_cycle()*](trait.Itertools.html#method.intersperse_cycle) for more information.
+pub struct IntersperseCycle<I> where
+    I: Iterator,
+{
+    seps: Vec<I::Item>,
+    index: usize,
+    iter: Fuse<I>,
+    peek: Option<I::Item>,
+}
+
+impl<I> IntersperseCycle<I> where
+    I: Iterator,
+{
+    /// Create a new IntersperseCycle iterator
+    ///
+    /// **Panics** if `seps` is empty.
+    pub fn new(iter: I, seps: Vec<I::Item>) -> Self
+    {
+        assert!(!seps.is_empty());
+        let mut iter = iter.fuse();
+        IntersperseCycle{peek: iter.next(), iter: iter, seps: seps, index: 0}
+    }
+}
+
+impl<I> Iterator for IntersperseCycle<I> where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = I::Item;
+    #[inline]
+    fn next(&mut self) -> Option<I::Item>
+    {
+        if self.peek.is_some() {
+            self.peek.take()
+        } else {
+            self.peek = self.iter.next();
+            if self.peek.is_some() {
+                let sep = self.seps[self.index].clone();
+                self.index = (self.index + 1) % self.seps.len();
+                Some(sep)
+            } else {
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        // 2 * SH + { 1 or 0 }
+        let has_peek = self.peek.is_some() as usize;
+        let sh = self.iter.size_hint();
+        size_hint::add_scalar(
+            size_hint::add(sh, sh), has_peek)
+    }
+}
+
+#[derive(Clone)]
+/// An iterator adaptor to insert a separator generated by a closure
+/// between each element of the adapted iterator.
+///
+/// Iterator element type is `I::Item`
+///
+/// This iterator is *fused*.
+///
+/// See [*.intersperse_with()*](trait.Itertools.html#method.intersperse_with) for more information.
+pub struct IntersperseWith<I, F> where
+    I: Iterator,
+{
+    f: F,
+    iter: Fuse<I>,
+    peek: Option<I::Item>,
+}
+
+impl<I, F> IntersperseWith<I, F> where
+    I: Iterator,
+{
+    /// Create a new IntersperseWith iterator
+    pub fn new(iter: I, f: F) -> Self
+    {
+        let mut iter = iter.fuse();
+        IntersperseWith{peek: iter.next(), iter: iter, f: f}
+    }
+}
+
+impl<I, F> Iterator for IntersperseWith<I, F> where
+    I: Iterator,
+    F: FnMut() -> I::Item,
+{
+    type Item = I::Item;
+    #[inline]
+    fn next(&mut self) -> Option<I::Item>
+    {
+        if self.peek.is_some() {
+            self.peek.take()
+        } else {
+            self.peek = self.iter.next();
+            if self.peek.is_some() {
+                Some((self.f)())
             } else {
                 None
             }
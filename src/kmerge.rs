@@ -0,0 +1,238 @@
+use std::mem;
+use size_hint;
+
+/// A sequence with its first element taken off, ordered in the heap by the
+/// head and then by the sequence's position in the input.
+struct HeadTail<I> where
+    I: Iterator,
+{
+    head: I::Item,
+    tail: I,
+    index: usize,
+}
+
+impl<I> Clone for HeadTail<I> where
+    I: Iterator + Clone,
+    I::Item: Clone,
+{
+    fn clone(&self) -> Self
+    {
+        HeadTail {
+            head: self.head.clone(),
+            tail: self.tail.clone(),
+            index: self.index,
+        }
+    }
+}
+
+/// Take the first element off each of the `iterables`, dropping the empty ones.
+fn head_tails<I>(iterables: I) -> Vec<HeadTail<<I::Item as IntoIterator>::IntoIter>> where
+    I: IntoIterator,
+    I::Item: IntoIterator,
+{
+    let mut heap = Vec::new();
+    for (index, iterable) in iterables.into_iter().enumerate() {
+        let mut tail = iterable.into_iter();
+        if let Some(head) = tail.next() {
+            heap.push(HeadTail { head: head, tail: tail, index: index });
+        }
+    }
+    heap
+}
+
+/// Restore the heap property below position `pos`, where `less_than`
+/// orders the heads of the sequences.
+fn sift_down<I, S>(heap: &mut [HeadTail<I>], mut pos: usize, less_than: &mut S) where
+    I: Iterator,
+    S: FnMut(&I::Item, &I::Item) -> bool,
+{
+    loop {
+        let mut least = pos;
+        for child in 2 * pos + 1..2 * pos + 3 {
+            if child < heap.len() {
+                let (c, l) = (&heap[child], &heap[least]);
+                // break ties by position to keep the merge stable
+                let less = less_than(&c.head, &l.head) ||
+                    (!less_than(&l.head, &c.head) && c.index < l.index);
+                if less {
+                    least = child;
+                }
+            }
+        }
+        if least == pos {
+            return;
+        }
+        heap.swap(pos, least);
+        pos = least;
+    }
+}
+
+fn heapify<I, S>(heap: &mut [HeadTail<I>], less_than: &mut S) where
+    I: Iterator,
+    S: FnMut(&I::Item, &I::Item) -> bool,
+{
+    for i in (0..heap.len() / 2).rev() {
+        sift_down(heap, i, less_than);
+    }
+}
+
+/// Pop the least element off the heap and advance its sequence.
+fn next_with<I, S>(heap: &mut Vec<HeadTail<I>>, less_than: &mut S) -> Option<I::Item> where
+    I: Iterator,
+    S: FnMut(&I::Item, &I::Item) -> bool,
+{
+    if heap.is_empty() {
+        return None;
+    }
+    let elt = match heap[0].tail.next() {
+        Some(next) => mem::replace(&mut heap[0].head, next),
+        None => heap.swap_remove(0).head,
+    };
+    sift_down(heap, 0, less_than);
+    Some(elt)
+}
+
+fn heap_size_hint<I>(heap: &[HeadTail<I>]) -> (usize, Option<usize>) where
+    I: Iterator,
+{
+    heap.iter()
+        .map(|ht| size_hint::add_scalar(ht.tail.size_hint(), 1))
+        .fold((0, Some(0)), size_hint::add)
+}
+
+/// An iterator adaptor that merges any number of sorted iterators into
+/// one sorted sequence.
+///
+/// Iterator element type is `I::Item`.
+///
+/// See [*kmerge()*](fn.kmerge.html) for more information.
+pub struct KMerge<I> where
+    I: Iterator,
+{
+    heap: Vec<HeadTail<I>>,
+}
+
+impl<I> Clone for KMerge<I> where
+    I: Iterator + Clone,
+    I::Item: Clone,
+{
+    fn clone(&self) -> Self
+    {
+        KMerge { heap: self.heap.clone() }
+    }
+}
+
+/// Merge the sorted iterators of `iterables` into one sorted sequence.
+///
+/// The sequences are kept in a binary heap ordered by their first
+/// element. Each input must already be sorted; equal elements are yielded
+/// in the order of the iterators they come from.
+///
+/// Iterator element type is `I::Item`.
+///
+/// ```
+/// use itertools::kmerge;
+///
+/// let it = kmerge(vec![vec![0, 3, 6], vec![1, 4, 7], vec![2, 5, 8]]);
+/// itertools::assert_equal(it, 0..9);
+/// ```
+pub fn kmerge<I>(iterables: I) -> KMerge<<I::Item as IntoIterator>::IntoIter> where
+    I: IntoIterator,
+    I::Item: IntoIterator,
+    <I::Item as IntoIterator>::Item: PartialOrd,
+{
+    let mut heap = head_tails(iterables);
+    heapify(&mut heap, &mut |a, b| a < b);
+    KMerge { heap: heap }
+}
+
+impl<I> Iterator for KMerge<I> where
+    I: Iterator,
+    I::Item: PartialOrd,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item>
+    {
+        next_with(&mut self.heap, &mut |a, b| a < b)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        heap_size_hint(&self.heap)
+    }
+}
+
+/// An iterator adaptor that merges any number of sorted iterators into
+/// one sorted sequence, ordered by a key function.
+///
+/// Iterator element type is `I::Item`.
+///
+/// See [*kmerge_by_key()*](fn.kmerge_by_key.html) for more information.
+pub struct KMergeByKey<I, F> where
+    I: Iterator,
+{
+    heap: Vec<HeadTail<I>>,
+    key: F,
+}
+
+impl<I, F> Clone for KMergeByKey<I, F> where
+    I: Iterator + Clone,
+    I::Item: Clone,
+    F: Clone,
+{
+    fn clone(&self) -> Self
+    {
+        KMergeByKey {
+            heap: self.heap.clone(),
+            key: self.key.clone(),
+        }
+    }
+}
+
+/// Merge the sorted iterators of `iterables` into one sorted sequence,
+/// comparing elements by `key`.
+///
+/// Each input must already be sorted by `key`. Elements with equal keys
+/// are yielded in the order of the iterators they come from.
+///
+/// Iterator element type is `I::Item`.
+///
+/// ```
+/// use itertools::kmerge_by_key;
+///
+/// let a = vec![(1, "a"), (4, "a")];
+/// let b = vec![(2, "b"), (3, "b")];
+/// let c = vec![(0, "c"), (4, "c")];
+/// let it = kmerge_by_key(vec![a, b, c], |x| x.0);
+/// itertools::assert_equal(it.map(|x| x.1), vec!["c", "a", "b", "b", "a", "c"]);
+/// ```
+pub fn kmerge_by_key<I, K, F>(iterables: I, mut key: F) -> KMergeByKey<<I::Item as IntoIterator>::IntoIter, F> where
+    I: IntoIterator,
+    I::Item: IntoIterator,
+    K: Ord,
+    F: FnMut(&<I::Item as IntoIterator>::Item) -> K,
+{
+    let mut heap = head_tails(iterables);
+    heapify(&mut heap, &mut |a, b| key(a) < key(b));
+    KMergeByKey { heap: heap, key: key }
+}
+
+impl<I, K, F> Iterator for KMergeByKey<I, F> where
+    I: Iterator,
+    K: Ord,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item>
+    {
+        let key = &mut self.key;
+        next_with(&mut self.heap, &mut |a, b| key(a) < key(b))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        heap_size_hint(&self.heap)
+    }
+}
@@ -37,12 +37,16 @@
 
 use std::iter::{self, IntoIterator};
 use std::fmt::Write;
-use std::cmp::Ordering;
+use std::cmp::{self, Ordering};
 use std::fmt;
 use std::hash::Hash;
+use std::collections::{HashSet, VecDeque};
+use std::vec;
 
 pub use adaptors::{
     Dedup,
+    DedupBy,
+    DedupWithCount,
     Interleave,
     InterleaveShortest,
     Product,
@@ -50,9 +54,11 @@ pub use adaptors::{
     PutBackN,
     Batching,
     GroupBy,
+    GroupByCount,
     Step,
     Merge,
     MergeBy,
+    MergeByKey,
     MultiPeek,
     TakeWhileRef,
     WhileSome,
@@ -61,43 +67,96 @@ pub use adaptors::{
     Combinations,
     Unique,
     UniqueBy,
+    Surround,
+    RunningGcd,
+    RunningLcm,
+    LocalMaxima,
+    RunningCounts,
+    ZigzagChunks,
+    Gaps,
+    Interpolate,
+    Frames,
+    FillFailing,
+    FlattenMarked,
+    CircularPairs,
+    SampleExponential,
+    MergeRuns,
+    ZipShift,
+    BatchUntil,
+    Ungroup,
+    Cumulative,
+    Transitions,
+    RunSpans,
+    ProductDiagonal,
+    Palindrome,
+    VecChunks,
+    TupleWindows,
+    TakeWhileFold,
+    PowersetGray,
+    ZipWith,
+    RepeatZip,
+    Pairwise,
 };
 #[cfg(feature = "unstable")]
 pub use adaptors::EnumerateFrom;
+pub use ascending::{AscendingByKey, OutOfOrder};
 pub use format::Format;
 pub use groupbylazy::{ChunksLazy, Chunk, Chunks, GroupByLazy, Group, Groups};
-pub use intersperse::Intersperse;
+pub use intersperse::{Intersperse, IntersperseCycle, IntersperseWith};
 pub use islice::{ISlice};
-pub use pad_tail::PadUsing;
+pub use kmerge::{kmerge, kmerge_by_key, KMerge, KMergeByKey};
+pub use pad_tail::{PadUsing, PadToStride};
+pub use productslices::{product_slices, ProductSlices};
+pub use records::ParseError;
 pub use repeatn::RepeatN;
 pub use rciter::RcIter;
 pub use stride::Stride;
 pub use stride::StrideMut;
 pub use tee::Tee;
 pub use linspace::{linspace, Linspace};
+pub use multi_product::{multi_cartesian_product, MultiProduct};
 pub use sources::{
     RepeatCall,
     Unfold,
 };
+pub use zip_eq::ZipEq;
 pub use zip_longest::{ZipLongest, EitherOrBoth};
 pub use ziptuple::{Zip};
 #[cfg(feature = "unstable")]
 pub use ziptrusted::{ZipTrusted, TrustedIterator};
 pub use zipslices::ZipSlices;
+pub use with_position::{WithPosition, Position};
+pub use windows::{
+    WindowedStep,
+    Convolve,
+    WindowSums,
+    WithRollingHash,
+    WindowDistinctCount,
+    MapTriples,
+    DedupWindow,
+};
 mod adaptors;
+mod ascending;
 mod format;
 mod groupbylazy;
 mod intersperse;
 mod islice;
+mod kmerge;
 mod linspace;
+mod multi_product;
 pub mod misc;
 mod pad_tail;
+mod productslices;
+mod records;
 mod rciter;
 mod repeatn;
 mod sources;
 pub mod size_hint;
 mod stride;
 mod tee;
+mod windows;
+mod with_position;
+mod zip_eq;
 mod zip_longest;
 mod ziptuple;
 #[cfg(feature = "unstable")]
@@ -247,6 +306,51 @@ pub trait Itertools : Iterator {
         Intersperse::new(self, element)
     }
 
+    /// An iterator adaptor to insert separators between each element of the
+    /// adapted iterator, taking them in turn from `seps` and starting over
+    /// from the first when they run out.
+    ///
+    /// Iterator element type is `Self::Item`.
+    ///
+    /// This iterator is *fused*.
+    ///
+    /// **Panics** if `seps` is empty.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// itertools::assert_equal((0..4).intersperse_cycle(vec![8, 9]), vec![0, 8, 1, 9, 2, 8, 3]);
+    /// ```
+    fn intersperse_cycle(self, seps: Vec<Self::Item>) -> IntersperseCycle<Self> where
+        Self: Sized,
+        Self::Item: Clone
+    {
+        IntersperseCycle::new(self, seps)
+    }
+
+    /// An iterator adaptor to insert a separator generated by calling `f`
+    /// between each element of the adapted iterator.
+    ///
+    /// `f` is called once per separator, only when one is needed.
+    ///
+    /// Iterator element type is `Self::Item`.
+    ///
+    /// This iterator is *fused*.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut sep = 10;
+    /// let it = (0..3).intersperse_with(|| { sep += 1; sep });
+    /// itertools::assert_equal(it, vec![0, 11, 1, 12, 2]);
+    /// ```
+    fn intersperse_with<F>(self, f: F) -> IntersperseWith<Self, F> where
+        Self: Sized,
+        F: FnMut() -> Self::Item,
+    {
+        IntersperseWith::new(self, f)
+    }
+
     /// Create an iterator which iterates over both this and the specified
     /// iterator simultaneously, yielding pairs of two optional elements.
     ///
@@ -272,11 +376,34 @@ pub trait Itertools : Iterator {
         ZipLongest::new(self, other.into_iter())
     }
 
+    /// Create an iterator which iterates over both this and the specified
+    /// iterator simultaneously, yielding pairs of elements.
+    ///
+    /// **Panics** if the iterators reach an end and they are not of equal
+    /// lengths.
+    ///
+    /// ```rust
+    /// use itertools::Itertools;
+    /// let it = (0..3).zip_eq("abc".chars());
+    /// itertools::assert_equal(it, vec![(0, 'a'), (1, 'b'), (2, 'c')]);
+    /// ```
+    #[inline]
+    fn zip_eq<J>(self, other: J) -> ZipEq<Self, J::IntoIter> where
+        J: IntoIterator,
+        Self: Sized,
+    {
+        ZipEq::new(self, other.into_iter())
+    }
+
     /// A “meta iterator adaptor”. Its closure recives a reference to the iterator
     /// and may pick off as many elements as it likes, to produce the next iterator element.
     ///
     /// Iterator element type is `B`.
     ///
+    /// The adaptor's size hint is `(0, None)`; use
+    /// [*Batching::with_size_hint()*](struct.Batching.html#method.with_size_hint)
+    /// to compute one from the underlying iterator's.
+    ///
     /// ```
     /// use itertools::Itertools;
     ///
@@ -325,6 +452,24 @@ pub trait Itertools : Iterator {
         GroupBy::new(self, key)
     }
 
+    /// Count iterator elements in groups. Consecutive elements that map to
+    /// the same key (“runs”), are counted together, without storing them.
+    ///
+    /// Iterator element type is `(K, usize)`: the group's key and its length.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = "aaabcc".chars().group_by_count(|&c| c);
+    /// itertools::assert_equal(it, vec![('a', 3), ('b', 1), ('c', 2)]);
+    /// ```
+    fn group_by_count<K, F>(self, key: F) -> GroupByCount<K, Self, F>
+        where Self: Sized,
+              F: FnMut(&Self::Item) -> K,
+    {
+        GroupByCount::new(self, key)
+    }
+
 
     /// Return an iterable that can group iterator elements.
     /// Consecutive elements that map to the same key (“runs”), are assigned
@@ -508,6 +653,9 @@ pub trait Itertools : Iterator {
     /// Return an iterator adaptor that merges the two base iterators in ascending order.
     /// If both base iterators are sorted (ascending), the result is sorted.
     ///
+    /// The merge is stable: of two equal elements, the one from `self`
+    /// comes first.
+    ///
     /// Iterator element type is `Self::Item`.
     ///
     /// ```
@@ -550,6 +698,31 @@ pub trait Itertools : Iterator {
         adaptors::merge_by_new(self, other.into_iter(), is_first)
     }
 
+    /// Return an iterator adaptor that merges the two base iterators in
+    /// ascending order of the key returned by `key`.
+    /// If both base iterators are sorted (ascending) by that key, the result is sorted.
+    ///
+    /// Elements with equal keys are taken from `self` first.
+    ///
+    /// Iterator element type is `Self::Item`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let a = vec![(1, "a"), (4, "b")];
+    /// let b = vec![(2, "c"), (3, "d")];
+    /// let it = a.into_iter().merge_by_key(b, |x| x.0);
+    /// itertools::assert_equal(it, vec![(1, "a"), (2, "c"), (3, "d"), (4, "b")]);
+    /// ```
+    fn merge_by_key<J, K, F>(self, other: J, key: F) -> MergeByKey<Self, J::IntoIter, F> where
+        Self: Sized,
+        J: IntoIterator<Item=Self::Item>,
+        K: Ord,
+        F: FnMut(&Self::Item) -> K,
+    {
+        adaptors::merge_by_key_new(self, other.into_iter(), key)
+    }
+
     /// Return an iterator adaptor that iterates over the cartesian product of
     /// the element sets of two iterators `self` and `J`.
     ///
@@ -664,6 +837,52 @@ pub trait Itertools : Iterator {
         Dedup::new(self)
     }
 
+    /// Remove duplicates from sections of consecutive elements, where two
+    /// elements are duplicates if `same` returns `true` for them.
+    /// Only the first element of each section is kept.
+    ///
+    /// Iterator element type is `Self::Item`.
+    ///
+    /// This iterator is *fused*.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let data = vec!["a", "bb", "cc", "d"];
+    /// itertools::assert_equal(data.into_iter().dedup_by(|x, y| x.len() == y.len()),
+    ///                         vec!["a", "bb", "d"]);
+    /// ```
+    fn dedup_by<F>(self, same: F) -> DedupBy<Self, F>
+        where Self: Sized,
+              F: FnMut(&Self::Item, &Self::Item) -> bool,
+    {
+        DedupBy::new(self, same)
+    }
+
+    /// Remove duplicates from sections of consecutive identical elements,
+    /// yielding each remaining element together with the number of
+    /// elements in its section.
+    ///
+    /// This is a run-length encoding of the iterator.
+    ///
+    /// Iterator element type is `(usize, Self::Item)`.
+    ///
+    /// This iterator is *fused*.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let data = vec!['a', 'a', 'b', 'c', 'c'];
+    /// itertools::assert_equal(data.into_iter().dedup_with_count(),
+    ///                         vec![(2, 'a'), (1, 'b'), (2, 'c')]);
+    /// ```
+    fn dedup_with_count(self) -> DedupWithCount<Self>
+        where Self: Sized,
+              Self::Item: PartialEq,
+    {
+        DedupWithCount::new(self)
+    }
+
     /// Return an iterator adaptor that filters out elements that have
     /// already been produced once during the iteration. Duplicates
     /// are detected using hash and equality.
@@ -839,203 +1058,1122 @@ pub trait Itertools : Iterator {
         self.map(f)
     }
 
-    // non-adaptor methods
-
-    /// Find the position and value of the first element satisfying a predicate.
+    /// Return an iterator adaptor that yields `head`, then all elements
+    /// of the iterator, then `tail`.
     ///
-    /// The iterator is not advanced past the first element found.
+    /// Iterator element type is `Self::Item`.
+    ///
+    /// This iterator is *fused*.
     ///
     /// ```
     /// use itertools::Itertools;
     ///
-    /// let text = "Hα";
-    /// assert_eq!(text.chars().find_position(|ch| ch.is_lowercase()), Some((1, 'α')));
+    /// itertools::assert_equal((1..3).surround(0, 9), vec![0, 1, 2, 9]);
     /// ```
-    fn find_position<P>(&mut self, mut pred: P) -> Option<(usize, Self::Item)> where
-        P: FnMut(&Self::Item) -> bool,
+    fn surround(self, head: Self::Item, tail: Self::Item) -> Surround<Self> where
+        Self: Sized
     {
-        let mut index = 0usize;
-        for elt in self {
-            if pred(&elt) {
-                return Some((index, elt))
-            }
-            index += 1;
-        }
-        None
+        Surround::new(self, head, tail)
     }
 
-    /// Consume the first `n` elements of the iterator eagerly.
+    /// Return an iterator adaptor that yields windows of `size` elements
+    /// as vectors, advancing `step` elements between the start of each window.
     ///
-    /// Return actual number of elements consumed, until done or reaching the end.
+    /// With `step == size` the windows are non-overlapping chunks, with
+    /// `step == 1` they overlap fully. A trailing partial window is dropped.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`.
+    ///
+    /// **Panics** if `size` or `step` is 0.
     ///
     /// ```
     /// use itertools::Itertools;
     ///
-    /// let mut iter = "αβγ".chars();
-    /// iter.dropn(2);
-    /// itertools::assert_equal(iter, "γ".chars());
+    /// let it = (0..6).windowed_step(3, 2);
+    /// itertools::assert_equal(it, vec![vec![0, 1, 2], vec![2, 3, 4]]);
     /// ```
-    fn dropn(&mut self, mut n: usize) -> usize
+    fn windowed_step(self, size: usize, step: usize) -> WindowedStep<Self> where
+        Self: Sized,
+        Self::Item: Clone,
     {
-        // FIXME: Can we use .nth() somehow?
-        let start = n;
-        while n > 0 {
-            match self.next() {
-                Some(..) => n -= 1,
-                None => break
-            }
-        }
-        start - n
+        WindowedStep::new(self, size, step)
     }
 
-    /// Consume the first `n` elements from the iterator eagerly,
-    /// and return the same iterator again.
+    /// Return an iterator adaptor that yields the greatest common divisor
+    /// of all elements seen so far, using the Euclidean algorithm with the
+    /// previous accumulator.
     ///
-    /// It works similarly to *.skip(* `n` *)* except it is eager and
-    /// preserves the iterator type.
+    /// The first element yielded is the absolute value of the first
+    /// element of the iterator. For signed types, a divisor whose absolute
+    /// value is not representable is yielded as the type's `MIN`.
+    ///
+    /// Iterator element type is `Self::Item`.
     ///
     /// ```
     /// use itertools::Itertools;
     ///
-    /// let mut iter = "αβγ".chars().dropping(2);
-    /// itertools::assert_equal(iter, "γ".chars());
+    /// let it = vec![12, 18, 6, 5].into_iter().running_gcd();
+    /// itertools::assert_equal(it, vec![12, 6, 6, 1]);
     /// ```
-    fn dropping(mut self, n: usize) -> Self where
+    fn running_gcd(self) -> RunningGcd<Self> where
         Self: Sized,
+        Self::Item: misc::Gcd,
     {
-        if n > 0 {
-            self.nth(n - 1);
-        }
-        self
+        RunningGcd::new(self)
     }
 
-    /// Consume the last `n` elements from the iterator eagerly,
-    /// and return the same iterator again.
-    ///
-    /// This is only possible on double ended iterators. `n` may be
-    /// larger than the number of elements.
+    /// Return an iterator adaptor that yields the least common multiple
+    /// of all elements seen so far.
     ///
-    /// Note: This method is eager, dropping the back elements immediately and
-    /// preserves the iterator type.
+    /// Iterator element type is `Self::Item`.
     ///
     /// ```
     /// use itertools::Itertools;
     ///
-    /// let init = vec![0, 3, 6, 9].into_iter().dropping_back(1);
-    /// itertools::assert_equal(init, vec![0, 3, 6]);
+    /// let it = vec![4, 6, 5].into_iter().running_lcm();
+    /// itertools::assert_equal(it, vec![4, 12, 60]);
     /// ```
-    fn dropping_back(mut self, n: usize) -> Self where
+    fn running_lcm(self) -> RunningLcm<Self> where
         Self: Sized,
-        Self: DoubleEndedIterator,
+        Self::Item: misc::Gcd,
     {
-        self.by_ref().rev().dropn(n);
-        self
+        RunningLcm::new(self)
     }
 
-    /// Run the closure `f` eagerly on each element of the iterator.
+    /// Return an iterator adaptor that pairs each element with a flag
+    /// telling if it is a local maximum, i.e. strictly greater than both
+    /// of its neighbors.
     ///
-    /// Consumes the iterator until its end.
+    /// The first and the last element have only one neighbor, and are
+    /// never marked as local maxima.
+    ///
+    /// Iterator element type is `(bool, Self::Item)`.
+    ///
+    /// This iterator is *fused*.
     ///
     /// ```
-    /// use std::sync::mpsc::channel;
     /// use itertools::Itertools;
     ///
-    /// let (tx, rx) = channel();
+    /// let it = vec![1, 3, 2, 5, 4].into_iter().local_maxima();
+    /// itertools::assert_equal(it, vec![(false, 1), (true, 3), (false, 2),
+    ///                                  (true, 5), (false, 4)]);
+    /// ```
+    fn local_maxima(self) -> LocalMaxima<Self> where
+        Self: Sized,
+        Self::Item: PartialOrd + Clone,
+    {
+        LocalMaxima::new(self)
+    }
+
+    /// Return an iterator adaptor that pairs each element with the number
+    /// of times its key has appeared so far, including the current element.
     ///
-    /// // use .foreach() to apply a function to each value -- sending it
-    /// (0..5).map(|x| x * 2 + 1).foreach(|x| { tx.send(x).unwrap(); } );
+    /// Keys are computed with the keying function `key` and counted
+    /// in a hash map in the iterator.
     ///
-    /// drop(tx);
+    /// Iterator element type is `(Self::Item, usize)`.
     ///
-    /// itertools::assert_equal(rx.iter(), vec![1, 3, 5, 7, 9]);
     /// ```
-    fn foreach<F>(&mut self, mut f: F) where
-        F: FnMut(Self::Item),
+    /// use itertools::Itertools;
+    ///
+    /// let data = vec!["a", "b", "a", "a"];
+    /// itertools::assert_equal(data.into_iter().running_counts(|s| *s),
+    ///                         vec![("a", 1), ("b", 1), ("a", 2), ("a", 3)]);
+    /// ```
+    fn running_counts<K, F>(self, key: F) -> RunningCounts<Self, K, F> where
+        Self: Sized,
+        K: Eq + Hash,
+        F: FnMut(&Self::Item) -> K,
     {
-        for elt in self { f(elt) }
+        RunningCounts::new(self, key)
     }
 
-    /// `.collect_vec()` is simply a type specialization of `.collect()`,
-    /// for convenience.
-    fn collect_vec(self) -> Vec<Self::Item> where
+    /// Return an iterator adaptor that gathers elements into vectors of
+    /// `size` elements, reversing every other chunk (a boustrophedon order).
+    ///
+    /// The last chunk will be shorter if there aren't enough elements.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`.
+    ///
+    /// **Panics** if `size` is 0.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (1..7).zigzag_chunks(2);
+    /// itertools::assert_equal(it, vec![vec![1, 2], vec![4, 3], vec![5, 6]]);
+    /// ```
+    fn zigzag_chunks(self, size: usize) -> ZigzagChunks<Self> where
         Self: Sized,
     {
-        self.collect()
+        ZigzagChunks::new(self, size)
     }
 
-    /// Assign to each reference in `self` from the `from` iterator,
-    /// stopping at the shortest of the two iterators.
+    /// Return an iterator adaptor that yields the gap between each pair of
+    /// consecutive elements, `next - previous`.
     ///
-    /// The `from` iterator is queried for its next element before the `self`
-    /// iterator, and if either is exhausted the method is done.
+    /// The iterator is assumed to be sorted in ascending order, so that
+    /// the gaps are never negative. An iterator with fewer than two
+    /// elements produces no gaps.
     ///
-    /// Return the number of elements written.
+    /// Iterator element type is `<Self::Item as Sub>::Output`.
+    ///
+    /// This iterator is *fused*.
     ///
     /// ```
     /// use itertools::Itertools;
     ///
-    /// let mut xs = [0; 4];
-    /// xs.iter_mut().set_from(1..);
-    /// assert_eq!(xs, [1, 2, 3, 4]);
+    /// let it = vec![1, 4, 5, 10].into_iter().gaps();
+    /// itertools::assert_equal(it, vec![3, 1, 5]);
     /// ```
-    #[inline]
-    fn set_from<'a, A: 'a, J>(&mut self, from: J) -> usize where
-        Self: Iterator<Item=&'a mut A>,
-        J: IntoIterator<Item=A>,
+    fn gaps(self) -> Gaps<Self> where
+        Self: Sized,
+        Self::Item: Clone + std::ops::Sub,
     {
-        let mut count = 0;
-        for elt in from {
-            match self.next() {
-                None => break,
-                Some(ptr) => *ptr = elt
-            }
-            count += 1;
-        }
-        count
+        Gaps::new(self)
     }
 
-    /// Combine all iterator elements into one String, seperated by `sep`.
+    /// Return an iterator adaptor that densifies a sequence of floats by
+    /// yielding each element followed by `steps` values linearly
+    /// interpolated between it and the next element (exclusive).
     ///
-    /// Use the `Display` implementation of each element.
+    /// The last element is yielded without any trailing interpolated values.
+    ///
+    /// Iterator element type is `Self::Item`.
+    ///
+    /// This iterator is *fused*.
     ///
     /// ```
     /// use itertools::Itertools;
     ///
-    /// assert_eq!(["a", "b", "c"].iter().join(", "), "a, b, c");
-    /// assert_eq!([1, 2, 3].iter().join(", "), "1, 2, 3");
+    /// let it = vec![0., 1., 3.].into_iter().interpolate(1);
+    /// itertools::assert_equal(it, vec![0., 0.5, 1., 2., 3.]);
     /// ```
-    fn join(&mut self, sep: &str) -> String where
-        Self::Item: std::fmt::Display,
+    fn interpolate(self, steps: usize) -> Interpolate<Self> where
+        Self: Sized,
     {
-        match self.next() {
-            None => String::new(),
-            Some(first_elt) => {
-                // estimate lower bound of capacity needed
-                let (lower, _) = self.size_hint();
-                let mut result = String::with_capacity(sep.len() * lower);
-                write!(&mut result, "{}", first_elt).unwrap();
-                for elt in self {
-                    result.push_str(sep);
-                    write!(&mut result, "{}", elt).unwrap();
-                }
-                result
-            }
-        }
+        Interpolate::new(self, steps)
     }
 
-    /// Format all iterator elements, separated by `sep`.
+    /// Return an iterator adaptor that splits the iterator into frames: each
+    /// frame is the vector of elements up to (and excluding) the next
+    /// element for which `is_sentinel` returns `true`.
     ///
-    /// The supplied closure `format` is called once per iterator element,
-    /// with two arguments: the element and a callback that takes a
-    /// `&Display` value, i.e. any reference to type that implements `Display`.
+    /// A trailing run of elements that is not terminated by a sentinel is
+    /// still yielded at the end. Consecutive sentinels produce empty frames.
     ///
-    /// Using `&format_args!(...)` is the most versatile way to apply custom
-    /// element formatting. The callback can be called multiple times if needed.
+    /// Iterator element type is `Vec<Self::Item>`.
+    ///
+    /// This iterator is *fused*.
     ///
     /// ```
     /// use itertools::Itertools;
     ///
-    /// let data = [1.1, 2.71828, -3.];
+    /// let data = vec![1, 2, 0, 3, 0, 4];
+    /// itertools::assert_equal(data.into_iter().frames(|x| *x == 0),
+    ///                         vec![vec![1, 2], vec![3], vec![4]]);
+    /// ```
+    fn frames<F>(self, is_sentinel: F) -> Frames<Self, F> where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        Frames::new(self, is_sentinel)
+    }
+
+    /// Return an iterator adaptor that passes through the elements for
+    /// which `pred` returns `true`, and replaces the others with a clone
+    /// of `fill`.
+    ///
+    /// Iterator element type is `Self::Item`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = vec![1, -2, 3, -4].into_iter().fill_failing(|x| *x >= 0, 0);
+    /// itertools::assert_equal(it, vec![1, 0, 3, 0]);
+    /// ```
+    fn fill_failing<F>(self, pred: F, fill: Self::Item) -> FillFailing<Self, F> where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        FillFailing::new(self, pred, fill)
+    }
+
+    /// Return an iterator adaptor that pairs each element with a flag that
+    /// is `true` for the first element of each run of consecutive elements
+    /// that map to the same key.
+    ///
+    /// This allows a consumer to reconstruct the groups of a flat stream.
+    ///
+    /// Iterator element type is `(bool, Self::Item)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let data = vec!["a", "a", "b", "a"];
+    /// itertools::assert_equal(data.into_iter().flatten_marked(|s| *s),
+    ///                         vec![(true, "a"), (false, "a"), (true, "b"), (true, "a")]);
+    /// ```
+    fn flatten_marked<K, F>(self, key: F) -> FlattenMarked<Self, K, F> where
+        Self: Sized,
+        K: PartialEq,
+        F: FnMut(&Self::Item) -> K,
+    {
+        FlattenMarked::new(self, key)
+    }
+
+    /// Return an iterator adaptor that yields the dot product of each
+    /// sliding window of the iterator with `kernel`, for example to compute
+    /// a weighted moving average.
+    ///
+    /// The windows have the same length as the kernel, and nothing is
+    /// yielded until a full window is available.
+    ///
+    /// Iterator element type is `Self::Item`.
+    ///
+    /// **Panics** if `kernel` is empty.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = vec![1., 2., 3., 4.].into_iter().convolve(vec![0.5, 0.5]);
+    /// itertools::assert_equal(it, vec![1.5, 2.5, 3.5]);
+    /// ```
+    fn convolve(self, kernel: Vec<Self::Item>) -> Convolve<Self> where
+        Self: Sized,
+    {
+        Convolve::new(self, kernel)
+    }
+
+    /// Return an iterator adaptor that yields each pair of adjacent
+    /// elements, followed by the pair that wraps around from the last
+    /// element to the first.
+    ///
+    /// An iterator of a single element `a` yields the pair `(a, a)`.
+    ///
+    /// Iterator element type is `(Self::Item, Self::Item)`.
+    ///
+    /// This iterator is *fused*.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = "abc".chars().circular_pairs();
+    /// itertools::assert_equal(it, vec![('a', 'b'), ('b', 'c'), ('c', 'a')]);
+    /// ```
+    fn circular_pairs(self) -> CircularPairs<Self> where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        CircularPairs::new(self)
+    }
+
+    /// Return an iterator adaptor that yields elements at exponentially
+    /// increasing distances, skipping the rest.
+    ///
+    /// The element at index 0 is yielded first, then each following
+    /// element is `base` times further from the previous one than the last
+    /// distance was: the yielded indices are *0*, *1*, *1 + base*,
+    /// *1 + base + base²*, … i.e. index *k* is *base⁰ + base¹ + … + baseᵏ⁻¹*.
+    ///
+    /// Iterator element type is `Self::Item`.
+    ///
+    /// **Panics** if `base` is 0.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (0..16).sample_exponential(2);
+    /// itertools::assert_equal(it, vec![0, 1, 3, 7, 15]);
+    /// ```
+    fn sample_exponential(self, base: usize) -> SampleExponential<Self> where
+        Self: Sized,
+    {
+        SampleExponential::new(self, base)
+    }
+
+    /// Return an iterator adaptor that folds each run of consecutive
+    /// elements that map to the same key into a single element, using the
+    /// closure `combine`.
+    ///
+    /// The runs are determined by the keys of the original elements.
+    /// This is like `.group_by()` followed by a reduction of each group,
+    /// but without buffering the groups.
+    ///
+    /// Iterator element type is `Self::Item`.
+    ///
+    /// This iterator is *fused*.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let data = vec![("a", 1), ("a", 2), ("b", 3), ("a", 4)];
+    /// let it = data.into_iter().merge_runs(|x| x.0, |x, y| (x.0, x.1 + y.1));
+    /// itertools::assert_equal(it, vec![("a", 3), ("b", 3), ("a", 4)]);
+    /// ```
+    fn merge_runs<K, F, G>(self, key: F, combine: G) -> MergeRuns<Self, K, F, G> where
+        Self: Sized,
+        K: PartialEq,
+        F: FnMut(&Self::Item) -> K,
+        G: FnMut(Self::Item, Self::Item) -> Self::Item,
+    {
+        MergeRuns::new(self, key, combine)
+    }
+
+    /// Return an iterator adaptor that yields the sum of each sliding
+    /// window of `window` elements.
+    ///
+    /// The sum is maintained as a running sum, adding the entering element
+    /// and subtracting the leaving one, so each step takes constant time.
+    /// Nothing is yielded until `window` elements are available.
+    ///
+    /// **Note:** With floating point elements, the running sum can
+    /// accumulate rounding errors.
+    ///
+    /// Iterator element type is `Self::Item`.
+    ///
+    /// **Panics** if `window` is 0.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = vec![1, 2, 3, 4].into_iter().window_sums(2);
+    /// itertools::assert_equal(it, vec![3, 5, 7]);
+    /// ```
+    fn window_sums(self, window: usize) -> WindowSums<Self> where
+        Self: Sized,
+    {
+        WindowSums::new(self, window)
+    }
+
+    /// Return an iterator adaptor that yields each sliding window of
+    /// `window` elements, paired with the hash of the window computed by
+    /// `hash`.
+    ///
+    /// The hash is recomputed from the window contents at each position.
+    /// Nothing is yielded until `window` elements are available.
+    ///
+    /// Iterator element type is `(u64, Vec<Self::Item>)`.
+    ///
+    /// **Panics** if `window` is 0.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = "abcd".bytes().with_rolling_hash(2, |w| w[0] as u64 * 256 + w[1] as u64);
+    /// itertools::assert_equal(it.map(|(h, _)| h), vec![0x6162, 0x6263, 0x6364]);
+    /// ```
+    fn with_rolling_hash<F>(self, window: usize, hash: F) -> WithRollingHash<Self, F> where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&[Self::Item]) -> u64,
+    {
+        WithRollingHash::new(self, window, hash)
+    }
+
+    /// Return an iterator adaptor that pairs each element with the element
+    /// `k` places after it: `(x[i], x[i + k])` for every valid `i`.
+    ///
+    /// Up to `k` elements are buffered. This is the basis of computing
+    /// an autocorrelation at lag `k`.
+    ///
+    /// Iterator element type is `(Self::Item, Self::Item)`.
+    ///
+    /// This iterator is *fused*.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (0..5).zip_shift(2);
+    /// itertools::assert_equal(it, vec![(0, 2), (1, 3), (2, 4)]);
+    /// ```
+    fn zip_shift(self, k: usize) -> ZipShift<Self> where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        ZipShift::new(self, k)
+    }
+
+    /// Return an iterator adaptor that collects the elements into batches.
+    ///
+    /// After each element is added to the current batch, `f` is called
+    /// with the batch so far; when it returns `true`, the batch is yielded
+    /// and a new one is started. The last batch is yielded even if `f`
+    /// never returned `true` for it.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`.
+    ///
+    /// This iterator is *fused*.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// // end a batch after each negative number
+    /// let it = vec![1, -2, 3, 4, -5, 6].into_iter()
+    ///     .batch_until(|batch| batch[batch.len() - 1] < 0);
+    /// itertools::assert_equal(it, vec![vec![1, -2], vec![3, 4, -5], vec![6]]);
+    /// ```
+    fn batch_until<F>(self, f: F) -> BatchUntil<Self, F> where
+        Self: Sized,
+        F: FnMut(&[Self::Item]) -> bool,
+    {
+        BatchUntil::new(self, f)
+    }
+
+    /// Return an iterator adaptor that flattens an iterator of groups
+    /// `(key, values)` into `(key, value)` pairs, in order.
+    ///
+    /// This is the inverse of collecting the groups of `.group_by()`.
+    ///
+    /// Iterator element type is `(K, V)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let groups = vec![("a", vec![1, 2]), ("b", vec![3])];
+    /// itertools::assert_equal(groups.into_iter().ungroup(),
+    ///                         vec![("a", 1), ("a", 2), ("b", 3)]);
+    /// ```
+    fn ungroup<K, V>(self) -> Ungroup<Self, K, V> where
+        Self: Sized + Iterator<Item=(K, Vec<V>)>,
+        K: Clone,
+    {
+        Ungroup::new(self)
+    }
+
+    /// Return an iterator adaptor that yields the running total of the
+    /// counts after each element, such as the cumulative distribution of
+    /// a histogram.
+    ///
+    /// Iterator element type is `usize`.
+    ///
+    /// **Panics** if the running total overflows `usize`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = vec![1, 2, 3, 4].into_iter().cumulative();
+    /// itertools::assert_equal(it, vec![1, 3, 6, 10]);
+    /// ```
+    fn cumulative(self) -> Cumulative<Self> where
+        Self: Sized + Iterator<Item=usize>,
+    {
+        Cumulative::new(self)
+    }
+
+    /// Return an iterator adaptor that yields each distinct transition
+    /// `(from, to)` between adjacent elements, once, in the order they are
+    /// first seen.
+    ///
+    /// Iterator element type is `(Self::Item, Self::Item)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = "abababc".chars().transitions();
+    /// itertools::assert_equal(it, vec![('a', 'b'), ('b', 'a'), ('b', 'c')]);
+    /// ```
+    fn transitions(self) -> Transitions<Self> where
+        Self: Sized,
+        Self::Item: Clone + Eq + Hash,
+    {
+        Transitions::new(self)
+    }
+
+    /// Return an iterator adaptor that wraps each element in `Some` and,
+    /// after the end of the sequence, yields `None` until the total number
+    /// of elements is a multiple of `stride`.
+    ///
+    /// This is useful to lay out a sequence in rows of equal length.
+    ///
+    /// Iterator element type is `Option<Self::Item>`.
+    ///
+    /// **Panics** if `stride` is 0.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (0..5).pad_to_stride(3);
+    /// itertools::assert_equal(it, vec![Some(0), Some(1), Some(2), Some(3), Some(4), None]);
+    /// ```
+    fn pad_to_stride(self, stride: usize) -> PadToStride<Self> where
+        Self: Sized,
+    {
+        PadToStride::new(self, stride)
+    }
+
+    /// Return an iterator adaptor that yields each run of consecutive
+    /// elements mapping to the same key as `(key, start, end)`, where
+    /// `start..end` is the run's index range in the original sequence.
+    ///
+    /// Iterator element type is `(K, usize, usize)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = "aabaaa".chars().run_spans(|&c| c);
+    /// itertools::assert_equal(it, vec![('a', 0, 2), ('b', 2, 3), ('a', 3, 6)]);
+    /// ```
+    fn run_spans<K, F>(self, key: F) -> RunSpans<Self, K, F> where
+        Self: Sized,
+        K: PartialEq,
+        F: FnMut(&Self::Item) -> K,
+    {
+        RunSpans::new(self, key)
+    }
+
+    /// Return an iterator adaptor that iterates over the cartesian product of
+    /// the element sets of two iterators `self` and `J`, along anti-diagonals.
+    ///
+    /// The pair of the elements at indices `i` and `j` is yielded before
+    /// every pair whose indices have a greater sum `i + j`. Unlike
+    /// `.cartesian_product()`, every pair is eventually reached even if
+    /// one or both of the iterators are infinite.
+    ///
+    /// Elements of both iterators are buffered as they are needed.
+    ///
+    /// Iterator element type is `(Self::Item, J::Item)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (0..).product_diagonal("ab".chars());
+    /// itertools::assert_equal(it.take(5),
+    ///                         vec![(0, 'a'), (0, 'b'), (1, 'a'), (1, 'b'), (2, 'a')]);
+    /// ```
+    fn product_diagonal<J>(self, other: J) -> ProductDiagonal<Self, J::IntoIter> where
+        Self: Sized,
+        Self::Item: Clone,
+        J: IntoIterator,
+        J::Item: Clone,
+    {
+        ProductDiagonal::new(self, other.into_iter())
+    }
+
+    /// Return an iterator adaptor that yields the elements of the iterator,
+    /// and then the same elements in reverse order back to the first.
+    ///
+    /// The last element is the middle of the palindrome and is yielded
+    /// only once; the elements are buffered to be yielded again.
+    ///
+    /// Iterator element type is `Self::Item`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// itertools::assert_equal((1..4).palindrome(), vec![1, 2, 3, 2, 1]);
+    /// ```
+    fn palindrome(self) -> Palindrome<Self> where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        Palindrome::new(self)
+    }
+
+    /// Return an iterator adaptor that yields the number of distinct
+    /// elements in each sliding window of `window` elements.
+    ///
+    /// The count of each element in the window is updated as the window
+    /// slides. Nothing is yielded until `window` elements are available.
+    ///
+    /// Iterator element type is `usize`.
+    ///
+    /// **Panics** if `window` is 0.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = "aabcc".chars().window_distinct_count(3);
+    /// itertools::assert_equal(it, vec![2, 3, 2]);
+    /// ```
+    fn window_distinct_count(self, window: usize) -> WindowDistinctCount<Self> where
+        Self: Sized,
+        Self::Item: Eq + Hash + Clone,
+    {
+        WindowDistinctCount::new(self, window)
+    }
+
+    /// Return an iterator adaptor that applies `f` to each triple of
+    /// adjacent elements, yielding one result per interior position.
+    ///
+    /// Nothing is yielded if there are fewer than three elements.
+    ///
+    /// Iterator element type is `B`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// // second differences
+    /// let it = vec![1, 4, 9, 16].into_iter().map_triples(|a, b, c| a - 2 * b + c);
+    /// itertools::assert_equal(it, vec![2, 2]);
+    /// ```
+    fn map_triples<B, F>(self, f: F) -> MapTriples<Self, F> where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&Self::Item, &Self::Item, &Self::Item) -> B,
+    {
+        MapTriples::new(self, f)
+    }
+
+    /// Return an iterator adaptor that gathers the elements into vectors of
+    /// `size` elements each. The last vector holds the remaining elements
+    /// and may be shorter.
+    ///
+    /// See also `.chunks_lazy()`, which does not allocate a vector per chunk.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`.
+    ///
+    /// **Panics** if `size` is 0.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (0..7).chunks(3);
+    /// itertools::assert_equal(it, vec![vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+    /// ```
+    fn chunks(self, size: usize) -> VecChunks<Self> where
+        Self: Sized,
+    {
+        VecChunks::new(self, size)
+    }
+
+    /// Return an iterator adaptor that yields each window of adjacent
+    /// elements as a tuple, without allocating.
+    ///
+    /// The window width is given by the tuple type: pairs, triples and
+    /// quadruples are supported. Each window is a clone of the elements.
+    /// Nothing is yielded if there are fewer elements than the window
+    /// width.
+    ///
+    /// Iterator element type is `T`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (1..5).tuple_windows::<(_, _)>();
+    /// itertools::assert_equal(it, vec![(1, 2), (2, 3), (3, 4)]);
+    ///
+    /// let it = (1..5).tuple_windows::<(_, _, _)>();
+    /// itertools::assert_equal(it, vec![(1, 2, 3), (2, 3, 4)]);
+    /// ```
+    fn tuple_windows<T>(self) -> TupleWindows<Self, T> where
+        Self: Sized + Iterator<Item=T::Item>,
+        T: misc::TupleCollect + Clone,
+    {
+        TupleWindows::new(self)
+    }
+
+    /// Return an iterator adaptor that folds the elements into an
+    /// accumulator, starting from `init`, and yields each element as long as
+    /// `pred` holds for the accumulator including it.
+    ///
+    /// The first element for which the updated accumulator fails `pred` is
+    /// consumed but not yielded, and the iteration ends.
+    ///
+    /// Iterator element type is `Self::Item`.
+    ///
+    /// This iterator is *fused*.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// // take elements while their sum stays within the budget
+    /// let it = (1..).take_while_fold(0, |sum, &x| sum + x, |&sum| sum <= 10);
+    /// itertools::assert_equal(it, vec![1, 2, 3, 4]);
+    /// ```
+    fn take_while_fold<B, F, G>(self, init: B, f: F, pred: G) -> TakeWhileFold<Self, B, F, G> where
+        Self: Sized,
+        F: FnMut(B, &Self::Item) -> B,
+        G: FnMut(&B) -> bool,
+    {
+        TakeWhileFold::new(self, init, f, pred)
+    }
+
+    /// Return an iterator adaptor that yields every subset of the elements,
+    /// as a vector, in Gray code order: each subset differs from the
+    /// previous one by exactly one element added or removed.
+    ///
+    /// The first subset is the empty one, and within each subset the
+    /// elements keep their original order. All elements are collected
+    /// when the adaptor is created.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = "ab".chars().powerset_gray();
+    /// itertools::assert_equal(it, vec![vec![], vec!['a'], vec!['a', 'b'], vec!['b']]);
+    /// ```
+    fn powerset_gray(self) -> PowersetGray<Self> where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        PowersetGray::new(self)
+    }
+
+    /// Return an iterator adaptor that pairs each element with a value
+    /// generated by calling `f`.
+    ///
+    /// `f` takes no arguments; it is called once per element, in order,
+    /// and only when an element is available.
+    ///
+    /// Iterator element type is `(Self::Item, B)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut serial = 100;
+    /// let it = "abc".chars().zip_with(|| { serial += 1; serial });
+    /// itertools::assert_equal(it, vec![('a', 101), ('b', 102), ('c', 103)]);
+    /// ```
+    fn zip_with<B, F>(self, f: F) -> ZipWith<Self, F> where
+        Self: Sized,
+        F: FnMut() -> B,
+    {
+        ZipWith::new(self, f)
+    }
+
+    /// Return an iterator adaptor that wraps each element in a `Position`
+    /// telling whether it is the first, a middle, the last, or the only
+    /// element.
+    ///
+    /// The adaptor looks one element ahead of the base iterator.
+    ///
+    /// Iterator element type is `Position<Self::Item>`.
+    ///
+    /// ```
+    /// use itertools::{Itertools, Position};
+    ///
+    /// let it = (0..4).with_position();
+    /// itertools::assert_equal(it, vec![Position::First(0),
+    ///                                  Position::Middle(1),
+    ///                                  Position::Middle(2),
+    ///                                  Position::Last(3)]);
+    ///
+    /// let it = (0..1).with_position();
+    /// itertools::assert_equal(it, vec![Position::Only(0)]);
+    /// ```
+    fn with_position(self) -> WithPosition<Self> where
+        Self: Sized,
+    {
+        WithPosition::new(self)
+    }
+
+    /// Return an iterator adaptor that repeats each element the number of
+    /// times given by the paired element of `counts`.
+    ///
+    /// A count of 0 skips the element. The iterator stops when either `self`
+    /// or `counts` runs out.
+    ///
+    /// Iterator element type is `Self::Item`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = "abc".chars().repeat_zip(vec![2, 0, 3]);
+    /// itertools::assert_equal(it, "aaccc".chars());
+    /// ```
+    fn repeat_zip<J>(self, counts: J) -> RepeatZip<Self, J::IntoIter> where
+        Self: Sized,
+        Self::Item: Clone,
+        J: IntoIterator<Item=usize>,
+    {
+        RepeatZip::new(self, counts.into_iter())
+    }
+
+    /// Return an iterator adaptor that checks that the keys of the elements,
+    /// as computed by `key`, are strictly increasing.
+    ///
+    /// Each element is yielded as `Ok`. The first element whose key is less
+    /// than or equal to the key before it is yielded as `Err(OutOfOrder)`
+    /// instead, and the iteration stops there.
+    ///
+    /// Iterator element type is `Result<Self::Item, OutOfOrder>`.
+    ///
+    /// This iterator is *fused*.
+    ///
+    /// ```
+    /// use itertools::{Itertools, OutOfOrder};
+    ///
+    /// let it = vec![(1, 'a'), (3, 'b'), (3, 'c')].into_iter().ascending_by_key(|x| x.0);
+    /// itertools::assert_equal(it, vec![Ok((1, 'a')), Ok((3, 'b')), Err(OutOfOrder)]);
+    /// ```
+    fn ascending_by_key<K, F>(self, key: F) -> AscendingByKey<Self, K, F> where
+        Self: Sized,
+        K: Ord,
+        F: FnMut(&Self::Item) -> K,
+    {
+        AscendingByKey::new(self, key)
+    }
+
+    /// Return an iterator adaptor that drops each element equal to one of
+    /// the last `window` elements it yielded.
+    ///
+    /// This lies between `.dedup()`, which compares only with the previous
+    /// element, and `.unique()`, which compares with all of them. A `window`
+    /// of 0 drops nothing.
+    ///
+    /// Iterator element type is `Self::Item`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = vec![1, 2, 1, 3, 1].into_iter().dedup_window(2);
+    /// itertools::assert_equal(it, vec![1, 2, 3, 1]);
+    /// ```
+    fn dedup_window(self, window: usize) -> DedupWindow<Self> where
+        Self: Sized,
+        Self::Item: Eq + Hash + Clone,
+    {
+        DedupWindow::new(self, window)
+    }
+
+    /// Return an iterator adaptor that applies `f` to each pair of
+    /// consecutive elements: `f(x0, x1)`, `f(x1, x2)`, and so on.
+    ///
+    /// An iterator of fewer than two elements yields nothing.
+    ///
+    /// Iterator element type is `B`.
+    ///
+    /// This iterator is *fused*.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let diffs = vec![1, 4, 9, 16].into_iter().pairwise(|a, b| b - a);
+    /// itertools::assert_equal(diffs, vec![3, 5, 7]);
+    /// ```
+    fn pairwise<B, F>(self, f: F) -> Pairwise<Self, F> where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(Self::Item, Self::Item) -> B,
+    {
+        Pairwise::new(self, f)
+    }
+
+    // non-adaptor methods
+
+    /// Find the position and value of the first element satisfying a predicate.
+    ///
+    /// The iterator is not advanced past the first element found.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let text = "Hα";
+    /// assert_eq!(text.chars().find_position(|ch| ch.is_lowercase()), Some((1, 'α')));
+    /// ```
+    fn find_position<P>(&mut self, mut pred: P) -> Option<(usize, Self::Item)> where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        let mut index = 0usize;
+        for elt in self {
+            if pred(&elt) {
+                return Some((index, elt))
+            }
+            index += 1;
+        }
+        None
+    }
+
+    /// Consume the first `n` elements of the iterator eagerly.
+    ///
+    /// Return actual number of elements consumed, until done or reaching the end.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut iter = "αβγ".chars();
+    /// iter.dropn(2);
+    /// itertools::assert_equal(iter, "γ".chars());
+    /// ```
+    fn dropn(&mut self, mut n: usize) -> usize
+    {
+        // FIXME: Can we use .nth() somehow?
+        let start = n;
+        while n > 0 {
+            match self.next() {
+                Some(..) => n -= 1,
+                None => break
+            }
+        }
+        start - n
+    }
+
+    /// Consume the first `n` elements from the iterator eagerly,
+    /// and return the same iterator again.
+    ///
+    /// It works similarly to *.skip(* `n` *)* except it is eager and
+    /// preserves the iterator type.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut iter = "αβγ".chars().dropping(2);
+    /// itertools::assert_equal(iter, "γ".chars());
+    /// ```
+    fn dropping(mut self, n: usize) -> Self where
+        Self: Sized,
+    {
+        if n > 0 {
+            self.nth(n - 1);
+        }
+        self
+    }
+
+    /// Consume the last `n` elements from the iterator eagerly,
+    /// and return the same iterator again.
+    ///
+    /// This is only possible on double ended iterators. `n` may be
+    /// larger than the number of elements.
+    ///
+    /// Note: This method is eager, dropping the back elements immediately and
+    /// preserves the iterator type.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let init = vec![0, 3, 6, 9].into_iter().dropping_back(1);
+    /// itertools::assert_equal(init, vec![0, 3, 6]);
+    /// ```
+    fn dropping_back(mut self, n: usize) -> Self where
+        Self: Sized,
+        Self: DoubleEndedIterator,
+    {
+        self.by_ref().rev().dropn(n);
+        self
+    }
+
+    /// Run the closure `f` eagerly on each element of the iterator.
+    ///
+    /// Consumes the iterator until its end.
+    ///
+    /// ```
+    /// use std::sync::mpsc::channel;
+    /// use itertools::Itertools;
+    ///
+    /// let (tx, rx) = channel();
+    ///
+    /// // use .foreach() to apply a function to each value -- sending it
+    /// (0..5).map(|x| x * 2 + 1).foreach(|x| { tx.send(x).unwrap(); } );
+    ///
+    /// drop(tx);
+    ///
+    /// itertools::assert_equal(rx.iter(), vec![1, 3, 5, 7, 9]);
+    /// ```
+    fn foreach<F>(&mut self, mut f: F) where
+        F: FnMut(Self::Item),
+    {
+        for elt in self { f(elt) }
+    }
+
+    /// `.collect_vec()` is simply a type specialization of `.collect()`,
+    /// for convenience.
+    fn collect_vec(self) -> Vec<Self::Item> where
+        Self: Sized,
+    {
+        self.collect()
+    }
+
+    /// Collect all iterator elements into a vector, reserving capacity
+    /// for the lower bound of the size hint up front.
+    ///
+    /// For iterators with an exact size hint this allocates once, with no
+    /// spare capacity. The upper bound is only a bound, not a length, so it
+    /// is never reserved: an iterator that may yield up to `usize::MAX`
+    /// elements but yields none should not try to allocate for all of them.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let v = (0..10).collect_vec_exact();
+    /// assert_eq!(v.len(), 10);
+    /// assert_eq!(v.capacity(), 10);
+    /// ```
+    fn collect_vec_exact(self) -> Vec<Self::Item> where
+        Self: Sized,
+    {
+        let (low, _) = self.size_hint();
+        let mut v = Vec::with_capacity(low);
+        v.extend(self);
+        v
+    }
+
+    /// Assign to each reference in `self` from the `from` iterator,
+    /// stopping at the shortest of the two iterators.
+    ///
+    /// The `from` iterator is queried for its next element before the `self`
+    /// iterator, and if either is exhausted the method is done.
+    ///
+    /// Return the number of elements written.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut xs = [0; 4];
+    /// xs.iter_mut().set_from(1..);
+    /// assert_eq!(xs, [1, 2, 3, 4]);
+    /// ```
+    #[inline]
+    fn set_from<'a, A: 'a, J>(&mut self, from: J) -> usize where
+        Self: Iterator<Item=&'a mut A>,
+        J: IntoIterator<Item=A>,
+    {
+        let mut count = 0;
+        for elt in from {
+            match self.next() {
+                None => break,
+                Some(ptr) => *ptr = elt
+            }
+            count += 1;
+        }
+        count
+    }
+
+    /// Combine all iterator elements into one String, seperated by `sep`.
+    ///
+    /// Use the `Display` implementation of each element.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// assert_eq!(["a", "b", "c"].iter().join(", "), "a, b, c");
+    /// assert_eq!([1, 2, 3].iter().join(", "), "1, 2, 3");
+    /// ```
+    fn join(&mut self, sep: &str) -> String where
+        Self::Item: std::fmt::Display,
+    {
+        match self.next() {
+            None => String::new(),
+            Some(first_elt) => {
+                // estimate lower bound of capacity needed
+                let (lower, _) = self.size_hint();
+                let mut result = String::with_capacity(sep.len() * lower);
+                write!(&mut result, "{}", first_elt).unwrap();
+                for elt in self {
+                    result.push_str(sep);
+                    write!(&mut result, "{}", elt).unwrap();
+                }
+                result
+            }
+        }
+    }
+
+    /// Format all iterator elements, separated by `sep`.
+    ///
+    /// The supplied closure `format` is called once per iterator element,
+    /// with two arguments: the element and a callback that takes a
+    /// `&Display` value, i.e. any reference to type that implements `Display`.
+    ///
+    /// Using `&format_args!(...)` is the most versatile way to apply custom
+    /// element formatting. The callback can be called multiple times if needed.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let data = [1.1, 2.71828, -3.];
     /// let data_formatter = data.iter().format(", ", |elt, f| f(&format_args!("{:2.2}", elt)));
     /// assert_eq!(format!("{}", data_formatter),
     ///            "1.10, 2.72, -3.00");
@@ -1199,6 +2337,368 @@ pub trait Itertools : Iterator {
         }
     }
 
+    /// Check that all pairs of elements from `self` and `other`, taken in
+    /// lockstep, satisfy the predicate `pred`.
+    ///
+    /// The two iterators are zipped together and the method stops at the
+    /// shorter of the two; trailing unmatched elements of the longer
+    /// iterator are ignored. Return `false` on the first pair that fails
+    /// the predicate, `true` otherwise.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// assert!((1..4).zip_all(vec![2, 3, 4], |a, b| b == a + 1));
+    /// assert!(!(1..4).zip_all(vec![2, 4], |a, b| b == a + 1));
+    /// ```
+    fn zip_all<J, F>(self, other: J, mut pred: F) -> bool
+        where Self: Sized,
+              J: IntoIterator,
+              F: FnMut(Self::Item, J::Item) -> bool,
+    {
+        for (a, b) in self.zip(other) {
+            if !pred(a, b) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Collect all iterator elements and return their prefix maximums and
+    /// suffix maximums.
+    ///
+    /// Element `i` of the first vector is the maximum of the elements
+    /// `0..i + 1`, and element `i` of the second vector is the maximum of
+    /// the elements `i..`.
+    ///
+    /// **Note:** This consumes the entire iterator.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let (prefix, suffix) = vec![2, 1, 3, 2].into_iter().prefix_suffix_max();
+    /// assert_eq!(prefix, vec![2, 2, 3, 3]);
+    /// assert_eq!(suffix, vec![3, 3, 3, 2]);
+    /// ```
+    fn prefix_suffix_max(self) -> (Vec<Self::Item>, Vec<Self::Item>)
+        where Self: Sized,
+              Self::Item: Ord + Clone,
+    {
+        let v: Vec<Self::Item> = self.collect();
+        let running_max = |acc: &mut Vec<Self::Item>, elt: &Self::Item| {
+            let max = match acc.last() {
+                Some(m) if *m >= *elt => m.clone(),
+                _ => elt.clone(),
+            };
+            acc.push(max);
+        };
+
+        let mut prefix = Vec::with_capacity(v.len());
+        for elt in &v {
+            running_max(&mut prefix, elt);
+        }
+        let mut suffix = Vec::with_capacity(v.len());
+        for elt in v.iter().rev() {
+            running_max(&mut suffix, elt);
+        }
+        suffix.reverse();
+        (prefix, suffix)
+    }
+
+    /// Count the number of distinct elements in the iterator.
+    ///
+    /// Duplicates are detected using hash and equality; the elements are
+    /// stored in a hash set while counting.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// assert_eq!(vec![1, 2, 2, 3, 1].into_iter().count_distinct(), 3);
+    /// ```
+    fn count_distinct(self) -> usize
+        where Self: Sized,
+              Self::Item: Eq + Hash,
+    {
+        let set: HashSet<Self::Item> = self.collect();
+        set.len()
+    }
+
+    /// Partition the iterator elements into `bins` vectors, greedily
+    /// balancing their total weight.
+    ///
+    /// Each element, in iteration order, is placed in the bin with the
+    /// currently lowest total weight (the first such bin on ties), where
+    /// an element's weight is given by `weight`.
+    ///
+    /// **Panics** if `bins` is 0.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let parts = vec![4, 3, 2, 1].into_iter().greedy_partition(2, |&x| x);
+    /// assert_eq!(parts, vec![vec![4, 1], vec![3, 2]]);
+    /// ```
+    fn greedy_partition<F>(self, bins: usize, mut weight: F) -> Vec<Vec<Self::Item>>
+        where Self: Sized,
+              F: FnMut(&Self::Item) -> u64,
+    {
+        assert!(bins != 0);
+        let mut parts: Vec<Vec<Self::Item>> = (0..bins).map(|_| Vec::new()).collect();
+        let mut totals = vec![0u64; bins];
+        for elt in self {
+            let mut lightest = 0;
+            for (i, &total) in totals.iter().enumerate() {
+                if total < totals[lightest] {
+                    lightest = i;
+                }
+            }
+            totals[lightest] += weight(&elt);
+            parts[lightest].push(elt);
+        }
+        parts
+    }
+
+    /// Distribute the iterator elements into `n` vectors round-robin: the
+    /// element at index `i` is placed in vector `i % n`.
+    ///
+    /// This is the inverse of interleaving `n` sequences.
+    ///
+    /// **Panics** if `n` is 0.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let parts = "abcde".chars().deinterleave(2);
+    /// assert_eq!(parts, vec![vec!['a', 'c', 'e'], vec!['b', 'd']]);
+    /// ```
+    fn deinterleave(self, n: usize) -> Vec<Vec<Self::Item>>
+        where Self: Sized,
+    {
+        assert!(n != 0);
+        let mut parts: Vec<Vec<Self::Item>> = (0..n).map(|_| Vec::new()).collect();
+        for (i, elt) in self.enumerate() {
+            parts[i % n].push(elt);
+        }
+        parts
+    }
+
+    /// Compute the mean and the sample variance of the iterator elements in
+    /// a single pass, using Welford's online algorithm.
+    ///
+    /// Return `None` if the iterator is empty. The variance of a single
+    /// element is `0.0`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let data = vec![1., 2., 3., 4.];
+    /// assert_eq!(data.into_iter().mean_variance(), Some((2.5, 5. / 3.)));
+    /// assert_eq!(Vec::<f64>::new().into_iter().mean_variance(), None);
+    /// ```
+    fn mean_variance(self) -> Option<(f64, f64)>
+        where Self: Sized,
+              Self::Item: Into<f64>,
+    {
+        let mut count = 0;
+        let mut mean = 0.;
+        // sum of squared differences from the current mean
+        let mut m2 = 0.;
+        for elt in self {
+            let x = elt.into();
+            count += 1;
+            let delta = x - mean;
+            mean += delta / count as f64;
+            m2 += delta * (x - mean);
+        }
+        match count {
+            0 => None,
+            1 => Some((mean, 0.)),
+            n => Some((mean, m2 / (n - 1) as f64)),
+        }
+    }
+
+    /// Consume the iterator and return an iterator over its last `n`
+    /// elements, in order.
+    ///
+    /// At most `n` elements are buffered at a time, so this works for
+    /// long sequences where only the tail is of interest.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// itertools::assert_equal((0..1000).last_n(3), vec![997, 998, 999]);
+    /// itertools::assert_equal((0..2).last_n(3), vec![0, 1]);
+    /// ```
+    fn last_n(self, n: usize) -> vec::IntoIter<Self::Item>
+        where Self: Sized,
+    {
+        let (low, _) = self.size_hint();
+        let mut tail = VecDeque::with_capacity(cmp::min(n, low));
+        for elt in self {
+            tail.push_back(elt);
+            if tail.len() > n {
+                tail.pop_front();
+            }
+        }
+        Vec::from(tail).into_iter()
+    }
+
+    /// Parse the elements as a header followed by any number of records.
+    ///
+    /// `read_header` is called once and `read_record` is then called
+    /// repeatedly until the elements run out; each consumes the elements
+    /// it needs, and may put one back to look ahead. A reader returning
+    /// `None` is an error.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// // a record count, then the records
+    /// let data = vec![2, 1, 2, 3, 4];
+    /// let parsed = data.into_iter().parse_records(
+    ///     |it| it.next(),
+    ///     |it| match (it.next(), it.next()) {
+    ///         (Some(x), Some(y)) => Some((x, y)),
+    ///         _ => None,
+    ///     });
+    /// assert_eq!(parsed, Ok((2, vec![(1, 2), (3, 4)])));
+    /// ```
+    fn parse_records<H, R, FH, FR>(self, read_header: FH, mut read_record: FR)
+        -> Result<(H, Vec<R>), ParseError>
+        where Self: Sized,
+              FH: FnOnce(&mut PutBack<Self>) -> Option<H>,
+              FR: FnMut(&mut PutBack<Self>) -> Option<R>,
+    {
+        let mut iter = PutBack::new(self);
+        let header = match read_header(&mut iter) {
+            Some(header) => header,
+            None => return Err(ParseError::InvalidHeader),
+        };
+        let mut records = Vec::new();
+        while let Some(elt) = iter.next() {
+            iter.put_back(elt);
+            match read_record(&mut iter) {
+                Some(record) => records.push(record),
+                None => return Err(ParseError::InvalidRecord(records.len())),
+            }
+        }
+        Ok((header, records))
+    }
+
+    /// Return the length of the longest strictly increasing subsequence of
+    /// the elements.
+    ///
+    /// Uses patience sorting, keeping the least possible last element of an
+    /// increasing subsequence of each length, in `O(n log n)` time.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// assert_eq!(vec![3, 1, 4, 1, 5, 9, 2, 6].into_iter().lis_len(), 4);
+    /// ```
+    fn lis_len(self) -> usize
+        where Self: Sized,
+              Self::Item: Ord,
+    {
+        let mut tails: Vec<Self::Item> = Vec::new();
+        for elt in self {
+            match tails.binary_search(&elt) {
+                // an equal element does not extend a strictly increasing subsequence
+                Ok(_) => {}
+                Err(i) if i == tails.len() => tails.push(elt),
+                Err(i) => tails[i] = elt,
+            }
+        }
+        tails.len()
+    }
+
+    /// Group the elements into the equivalence classes of the relation
+    /// `related`, closed under symmetry and transitivity.
+    ///
+    /// Elements are merged into classes with union-find. Each pair of
+    /// elements not already in the same class is tested, so `related` is
+    /// called `O(n²)` times.
+    ///
+    /// The classes are in the order of their first elements, and the
+    /// elements of each class are in their original order.
+    ///
+    /// **Note:** This consumes the entire iterator, buffering the elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let classes = vec![1, 2, 5, 6, 3].into_iter()
+    ///     .equivalence_classes(|a: &i32, b: &i32| (a - b).abs() <= 1);
+    /// assert_eq!(classes, vec![vec![1, 2, 3], vec![5, 6]]);
+    /// ```
+    fn equivalence_classes<F>(self, mut related: F) -> Vec<Vec<Self::Item>>
+        where Self: Sized,
+              F: FnMut(&Self::Item, &Self::Item) -> bool,
+    {
+        fn find(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+
+        let elts: Vec<Self::Item> = self.collect();
+        let mut parent: Vec<usize> = (0..elts.len()).collect();
+        for i in 0..elts.len() {
+            for j in i + 1..elts.len() {
+                let (a, b) = (find(&mut parent, i), find(&mut parent, j));
+                if a != b && related(&elts[i], &elts[j]) {
+                    // the root of a class is its first element
+                    if a < b {
+                        parent[b] = a;
+                    } else {
+                        parent[a] = b;
+                    }
+                }
+            }
+        }
+
+        let mut class_index = vec![0; elts.len()];
+        let mut classes: Vec<Vec<Self::Item>> = Vec::new();
+        for (i, elt) in elts.into_iter().enumerate() {
+            let root = find(&mut parent, i);
+            if root == i {
+                class_index[i] = classes.len();
+                classes.push(vec![elt]);
+            } else {
+                classes[class_index[root]].push(elt);
+            }
+        }
+        classes
+    }
+
+    /// Collect the yielded indices into a bitset of `len` bits, stored in
+    /// `u64` words.
+    ///
+    /// Bit `i` is bit `i % 64` of word `i / 64`. The bitset has
+    /// `(len + 63) / 64` words, and bits past `len` in the last word are
+    /// left unset.
+    ///
+    /// **Panics** if an index is `len` or larger.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let bits = vec![1, 3, 64].into_iter().to_bitset(100);
+    /// assert_eq!(bits, vec![0b1010, 1]);
+    /// ```
+    fn to_bitset(self, len: usize) -> Vec<u64>
+        where Self: Sized + Iterator<Item=usize>,
+    {
+        let mut bits = vec![0; (len + 63) / 64];
+        for i in self {
+            assert!(i < len, "itertools: .to_bitset() index {} out of range", i);
+            bits[i / 64] |= 1 << (i % 64);
+        }
+        bits
+    }
+
     /// Collect all iterator elements into a sorted vector.
     ///
     /// **Note:** This consumes the entire iterator, uses the
@@ -1229,6 +2729,55 @@ pub trait Itertools : Iterator {
         v
     }
 
+    /// Collect all iterator elements into a sorted vector,
+    /// with duplicates removed.
+    ///
+    /// **Note:** This consumes the entire iterator. After sorting, equal
+    /// elements are adjacent, so they are removed with `Vec::dedup()`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// assert_eq!(vec![3, 1, 2, 3, 1].into_iter().sorted_unique(),
+    ///            vec![1, 2, 3]);
+    /// ```
+    fn sorted_unique(self) -> Vec<Self::Item>
+        where Self: Sized,
+              Self::Item: Ord,
+    {
+        let mut v: Vec<Self::Item> = self.collect();
+
+        v.sort();
+        v.dedup();
+        v
+    }
+
+    /// Return the indices that would sort the iterator elements.
+    ///
+    /// The sort is stable: equal elements keep their original order.
+    ///
+    /// **Note:** This consumes the entire iterator, buffering the elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let ages = [30, 10, 20];
+    /// let names = ["Jill", "John", "Jane"];
+    /// let order = ages.iter().argsort();
+    /// assert_eq!(order, vec![1, 2, 0]);
+    /// itertools::assert_equal(order.iter().map(|&i| names[i]), vec!["John", "Jane", "Jill"]);
+    /// ```
+    fn argsort(self) -> Vec<usize>
+        where Self: Sized,
+              Self::Item: Ord,
+    {
+        let v: Vec<Self::Item> = self.collect();
+        let mut indices: Vec<usize> = (0..v.len()).collect();
+
+        indices.sort_by(|&a, &b| v[a].cmp(&v[b]));
+        indices
+    }
+
     /// **Deprecated:** renamed to `.sorted_by()`
     fn sort_by<F>(self, cmp: F) -> Vec<Self::Item>
         where Self: Sized,
@@ -1343,6 +2892,28 @@ pub fn partition<'a, A: 'a, I, F>(iter: I, mut pred: F) -> usize where
 }
 
 
+/// Return the sizes of the intersection and the union of the distinct
+/// elements of `a` and `b`, as `(intersection, union)`.
+///
+/// The Jaccard similarity of the two sets is `intersection / union`.
+/// The elements of both iterables are stored in hash sets.
+///
+/// ```
+/// use itertools::overlap;
+///
+/// assert_eq!(overlap(vec![1, 2, 3], vec![2, 3, 4]), (2, 4));
+/// ```
+pub fn overlap<I, J>(a: I, b: J) -> (usize, usize)
+    where I: IntoIterator,
+          J: IntoIterator<Item=I::Item>,
+          I::Item: Eq + Hash,
+{
+    let a: HashSet<_> = a.into_iter().collect();
+    let b: HashSet<_> = b.into_iter().collect();
+    let intersection = a.intersection(&b).count();
+    (intersection, a.len() + b.len() - intersection)
+}
+
 /// Iterate `iterable` with a running index.
 ///
 /// `IntoIterator` enabled version of `.enumerate()`.
@@ -230,3 +230,125 @@ unsafe impl<'a, T> Slice for &'a mut [T] {
         (*(*self as *mut [T])).get_unchecked_mut(i)
     }
 }
+
+/// A helper trait for integer types, computing greatest common divisors
+/// and least common multiples. Used by `.running_gcd()` and `.running_lcm()`.
+///
+/// For signed types the result is the absolute value, except where that is
+/// not representable: the gcd of `MIN` with `0` or `MIN`, and the lcm of
+/// `MIN` with `1` or `-1`, are returned as `MIN`.
+pub trait Gcd : Copy {
+    #[doc(hidden)]
+    /// Return the greatest common divisor, using the Euclidean algorithm.
+    fn gcd(self, other: Self) -> Self;
+    #[doc(hidden)]
+    /// Return the least common multiple.
+    fn lcm(self, other: Self) -> Self;
+}
+
+macro_rules! impl_gcd {
+    ($abs:expr; $($t:ty)*) => ($(
+        impl Gcd for $t {
+            #[inline]
+            fn gcd(self, other: $t) -> $t {
+                let (mut a, mut b) = (self, other);
+                while b != 0 {
+                    let r = a.wrapping_rem(b);
+                    a = b;
+                    b = r;
+                }
+                $abs(a)
+            }
+
+            #[inline]
+            fn lcm(self, other: $t) -> $t {
+                if self == 0 || other == 0 {
+                    0
+                } else {
+                    $abs(self / self.gcd(other)) * $abs(other)
+                }
+            }
+        }
+    )*);
+}
+
+impl_gcd!(|x| x; u8 u16 u32 u64 usize);
+impl_gcd!(|x: Self| x.wrapping_abs(); i8 i16 i32 i64 isize);
+
+/// A helper trait for tuples of two to four elements of the same type,
+/// used by `.tuple_windows()`.
+pub trait TupleCollect : Sized {
+    /// The type of the tuple's elements
+    type Item;
+    #[doc(hidden)]
+    /// Return the number of elements of the tuple.
+    fn num_items() -> usize;
+    #[doc(hidden)]
+    /// Collect a tuple from the next elements of `iter`, if there are enough.
+    fn collect_from_iter<I>(iter: &mut I) -> Option<Self>
+        where I: Iterator<Item=Self::Item>;
+    #[doc(hidden)]
+    /// Shift the elements one step to the left, dropping the first and
+    /// putting `item` last.
+    fn left_shift_push(&mut self, item: Self::Item);
+}
+
+impl<A> TupleCollect for (A, A) {
+    type Item = A;
+
+    fn num_items() -> usize { 2 }
+
+    fn collect_from_iter<I>(iter: &mut I) -> Option<Self>
+        where I: Iterator<Item=A>
+    {
+        match (iter.next(), iter.next()) {
+            (Some(a), Some(b)) => Some((a, b)),
+            _ => None,
+        }
+    }
+
+    fn left_shift_push(&mut self, item: A) {
+        self.0 = mem::replace(&mut self.1, item);
+    }
+}
+
+impl<A> TupleCollect for (A, A, A) {
+    type Item = A;
+
+    fn num_items() -> usize { 3 }
+
+    fn collect_from_iter<I>(iter: &mut I) -> Option<Self>
+        where I: Iterator<Item=A>
+    {
+        match (iter.next(), iter.next(), iter.next()) {
+            (Some(a), Some(b), Some(c)) => Some((a, b, c)),
+            _ => None,
+        }
+    }
+
+    fn left_shift_push(&mut self, item: A) {
+        let c = mem::replace(&mut self.2, item);
+        self.0 = mem::replace(&mut self.1, c);
+    }
+}
+
+impl<A> TupleCollect for (A, A, A, A) {
+    type Item = A;
+
+    fn num_items() -> usize { 4 }
+
+    fn collect_from_iter<I>(iter: &mut I) -> Option<Self>
+        where I: Iterator<Item=A>
+    {
+        match (iter.next(), iter.next(), iter.next(), iter.next()) {
+            (Some(a), Some(b), Some(c), Some(d)) => Some((a, b, c, d)),
+            _ => None,
+        }
+    }
+
+    fn left_shift_push(&mut self, item: A) {
+        let d = mem::replace(&mut self.3, item);
+        let c = mem::replace(&mut self.2, d);
+        self.0 = mem::replace(&mut self.1, c);
+    }
+}
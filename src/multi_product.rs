@@ -0,0 +1,147 @@
+use size_hint;
+
+/// One wheel of the `MultiProduct` odometer.
+#[derive(Clone)]
+struct MultiProductIter<I> where
+    I: Iterator,
+{
+    cur: Option<I::Item>,
+    iter: I,
+    iter_orig: I,
+}
+
+/// An iterator adaptor that iterates over the cartesian product of the
+/// element sets of any number of iterators.
+///
+/// Iterator element type is `Vec<I::Item>`.
+///
+/// This iterator is *fused*.
+///
+/// See [*multi_cartesian_product()*](fn.multi_cartesian_product.html) for more information.
+pub struct MultiProduct<I> where
+    I: Iterator,
+{
+    iters: Vec<MultiProductIter<I>>,
+    started: bool,
+    done: bool,
+}
+
+impl<I> Clone for MultiProduct<I> where
+    I: Iterator + Clone,
+    I::Item: Clone,
+{
+    fn clone(&self) -> Self
+    {
+        MultiProduct {
+            iters: self.iters.clone(),
+            started: self.started,
+            done: self.done,
+        }
+    }
+}
+
+/// Iterate over the cartesian product of the iterators of `iterables`,
+/// yielding each combination as a `Vec`.
+///
+/// The rows are in lexicographic order: the last iterator advances
+/// fastest, like the rightmost wheel of an odometer. If there are no
+/// iterators, or any of them is empty, the product is empty.
+///
+/// Iterator element type is `Vec<T>`, where `T` is the element type of the
+/// iterators.
+///
+/// ```
+/// use itertools::multi_cartesian_product;
+///
+/// let it = multi_cartesian_product(vec![0..2, 3..5]);
+/// itertools::assert_equal(it, vec![vec![0, 3], vec![0, 4], vec![1, 3], vec![1, 4]]);
+/// ```
+pub fn multi_cartesian_product<I>(iterables: I) -> MultiProduct<<I::Item as IntoIterator>::IntoIter> where
+    I: IntoIterator,
+    I::Item: IntoIterator,
+    <I::Item as IntoIterator>::IntoIter: Clone,
+    <I::Item as IntoIterator>::Item: Clone,
+{
+    let iters = iterables.into_iter().map(|iterable| {
+        let iter = iterable.into_iter();
+        MultiProductIter { cur: None, iter: iter.clone(), iter_orig: iter }
+    });
+    MultiProduct { iters: iters.collect(), started: false, done: false }
+}
+
+impl<I> MultiProduct<I> where
+    I: Iterator + Clone,
+    I::Item: Clone,
+{
+    /// Set every wheel to its first element; return whether they all have one.
+    fn start(&mut self) -> bool
+    {
+        for wheel in &mut self.iters {
+            wheel.cur = wheel.iter.next();
+            if wheel.cur.is_none() {
+                return false;
+            }
+        }
+        !self.iters.is_empty()
+    }
+
+    /// Advance the rightmost wheel, carrying into the wheels to its left;
+    /// return false when the leftmost wheel runs out.
+    fn step(&mut self) -> bool
+    {
+        for wheel in self.iters.iter_mut().rev() {
+            wheel.cur = wheel.iter.next();
+            if wheel.cur.is_some() {
+                return true;
+            }
+            wheel.iter = wheel.iter_orig.clone();
+            wheel.cur = wheel.iter.next();
+        }
+        false
+    }
+}
+
+impl<I> Iterator for MultiProduct<I> where
+    I: Iterator + Clone,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>>
+    {
+        if self.done {
+            return None;
+        }
+        let more = if self.started {
+            self.step()
+        } else {
+            self.started = true;
+            self.start()
+        };
+        if !more {
+            self.done = true;
+            return None;
+        }
+        Some(self.iters.iter().map(|wheel| wheel.cur.clone().unwrap()).collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        if self.done || self.iters.is_empty() {
+            return (0, Some(0));
+        }
+        if !self.started {
+            return self.iters.iter()
+                       .fold((1, Some(1)), |acc, wheel| size_hint::mul(acc, wheel.iter.size_hint()));
+        }
+        // each wheel's remaining elements, times a full turn of the wheels
+        // to its right
+        let mut remaining = (0, Some(0));
+        let mut turn = (1, Some(1));
+        for wheel in self.iters.iter().rev() {
+            remaining = size_hint::add(remaining, size_hint::mul(wheel.iter.size_hint(), turn));
+            turn = size_hint::mul(turn, wheel.iter_orig.size_hint());
+        }
+        remaining
+    }
+}
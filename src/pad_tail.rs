@@ -82,3 +82,66 @@ impl<I, F> ExactSizeIterator for PadUsing<I, F>
     where I: Iterator,
           F: FnMut(usize) -> I::Item,
 { }
+
+/// An iterator adaptor that wraps elements in `Some` and pads the sequence
+/// with `None` until its length is a multiple of a stride.
+///
+/// Iterator element type is **Option<I::Item>**.
+///
+/// See [*.pad_to_stride()*](trait.Itertools.html#method.pad_to_stride) for more information.
+#[derive(Clone)]
+pub struct PadToStride<I> {
+    iter: Fuse<I>,
+    stride: usize,
+    pos: usize,
+}
+
+impl<I> PadToStride<I> where
+    I: Iterator,
+{
+    /// Create a new **PadToStride** iterator.
+    ///
+    /// **Panics** if `stride` is 0.
+    pub fn new(iter: I, stride: usize) -> PadToStride<I> {
+        assert!(stride != 0);
+        PadToStride {
+            iter: iter.fuse(),
+            stride: stride,
+            pos: 0,
+        }
+    }
+}
+
+impl<I> Iterator for PadToStride<I> where
+    I: Iterator,
+{
+    type Item = Option<I::Item>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Option<I::Item>> {
+        match self.iter.next() {
+            None => {
+                if self.pos % self.stride != 0 {
+                    self.pos += 1;
+                    Some(None)
+                } else {
+                    None
+                }
+            },
+            e => {
+                self.pos += 1;
+                Some(e)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (pos, stride) = (self.pos, self.stride);
+        let padded = |n: usize| {
+            pos.checked_add(n).and_then(|total| total.checked_add(stride - 1))
+                .map(|total| total / stride * stride - pos)
+        };
+        let (low, hi) = self.iter.size_hint();
+        (padded(low).unwrap_or(low), hi.and_then(padded))
+    }
+}
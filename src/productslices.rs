@@ -0,0 +1,80 @@
+use size_hint;
+
+/// An iterator over the cartesian product of two slices, yielding
+/// pairs of references.
+///
+/// Iterator element type is `(&'a A, &'b B)`.
+///
+/// See [*product_slices()*](fn.product_slices.html) for more information.
+pub struct ProductSlices<'a, 'b, A: 'a, B: 'b> {
+    xs: &'a [A],
+    ys: &'b [B],
+    i: usize,
+    j: usize,
+}
+
+/// Iterate over the cartesian product of the slices `xs` and `ys`,
+/// yielding pairs of references.
+///
+/// Unlike `.cartesian_product()`, neither side needs to be cloned.
+///
+/// Iterator element type is `(&'a A, &'b B)`.
+///
+/// ```
+/// use itertools::product_slices;
+///
+/// let xs = [0, 1];
+/// let ys = ["a", "b"];
+/// itertools::assert_equal(product_slices(&xs, &ys),
+///                         vec![(&0, &"a"), (&0, &"b"), (&1, &"a"), (&1, &"b")]);
+/// ```
+pub fn product_slices<'a, 'b, A, B>(xs: &'a [A], ys: &'b [B]) -> ProductSlices<'a, 'b, A, B>
+{
+    ProductSlices {
+        xs: xs,
+        ys: ys,
+        i: 0,
+        j: 0,
+    }
+}
+
+impl<'a, 'b, A, B> Clone for ProductSlices<'a, 'b, A, B>
+{
+    fn clone(&self) -> Self
+    {
+        ProductSlices {
+            xs: self.xs,
+            ys: self.ys,
+            i: self.i,
+            j: self.j,
+        }
+    }
+}
+
+impl<'a, 'b, A, B> Iterator for ProductSlices<'a, 'b, A, B>
+{
+    type Item = (&'a A, &'b B);
+
+    #[inline]
+    fn next(&mut self) -> Option<(&'a A, &'b B)>
+    {
+        if self.i >= self.xs.len() || self.ys.is_empty() {
+            return None;
+        }
+        let elt = (&self.xs[self.i], &self.ys[self.j]);
+        self.j += 1;
+        if self.j == self.ys.len() {
+            self.j = 0;
+            self.i += 1;
+        }
+        Some(elt)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        // Not ExactSizeIterator because size may be larger than usize
+        let rows = self.xs.len().saturating_sub(self.i);
+        size_hint::sub_scalar(size_hint::mul_scalar((rows, Some(rows)), self.ys.len()),
+                              self.j)
+    }
+}
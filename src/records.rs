@@ -0,0 +1,29 @@
+use std::error::Error;
+use std::fmt;
+
+/// An error from [*.parse_records()*](trait.Itertools.html#method.parse_records).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The header could not be read.
+    InvalidHeader,
+    /// The record at the given index (counting from zero) could not be read.
+    InvalidRecord(usize),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::InvalidHeader => write!(f, "invalid header"),
+            ParseError::InvalidRecord(i) => write!(f, "invalid record at index {}", i),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn description(&self) -> &str {
+        match *self {
+            ParseError::InvalidHeader => "invalid header",
+            ParseError::InvalidRecord(_) => "invalid record",
+        }
+    }
+}
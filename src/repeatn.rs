@@ -1,5 +1,6 @@
 
 /// An iterator that repeats an element exactly *n* times.
+#[derive(Clone)]
 pub struct RepeatN<A>
 {
     elt: Option<A>,
@@ -0,0 +1,511 @@
+use std::cmp;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::iter::Fuse;
+use std::ops::{Add, Sub, Mul};
+use Itertools;
+use size_hint;
+
+/// An iterator adaptor that yields windows of a fixed size as `Vec`s,
+/// advancing a fixed number of elements between windows.
+///
+/// Iterator element type is `Vec<I::Item>`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.windowed_step()*](trait.Itertools.html#method.windowed_step) for more information.
+#[derive(Clone)]
+pub struct WindowedStep<I> where
+    I: Iterator,
+{
+    iter: Fuse<I>,
+    buf: Vec<I::Item>,
+    size: usize,
+    step: usize,
+    skip: usize,
+}
+
+impl<I> WindowedStep<I> where
+    I: Iterator,
+{
+    /// Create a new `WindowedStep` iterator.
+    ///
+    /// **Panics** if `size` or `step` is 0.
+    pub fn new(iter: I, size: usize, step: usize) -> Self
+    {
+        assert!(size != 0);
+        assert!(step != 0);
+        WindowedStep {
+            iter: iter.fuse(),
+            buf: Vec::new(),
+            size: size,
+            step: step,
+            skip: 0,
+        }
+    }
+}
+
+impl<I> Iterator for WindowedStep<I> where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>>
+    {
+        // elements skipped over when the step is larger than the window
+        self.iter.dropn(self.skip);
+        self.skip = 0;
+        while self.buf.len() < self.size {
+            match self.iter.next() {
+                Some(elt) => self.buf.push(elt),
+                None => return None,
+            }
+        }
+        let window = self.buf.clone();
+        if self.step >= self.size {
+            self.buf.clear();
+            self.skip = self.step - self.size;
+        } else {
+            self.buf.drain(..self.step);
+        }
+        Some(window)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        let windows = |n: usize| {
+            let n = n.saturating_sub(self.skip);
+            if n < self.size {
+                0
+            } else {
+                (n - self.size) / self.step + 1
+            }
+        };
+        let (low, hi) = size_hint::add_scalar(self.iter.size_hint(), self.buf.len());
+        (windows(low), hi.map(windows))
+    }
+}
+
+/// An iterator adaptor that yields the dot product of each sliding window
+/// of the base iterator with a kernel.
+///
+/// Iterator element type is `I::Item`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.convolve()*](trait.Itertools.html#method.convolve) for more information.
+#[derive(Clone)]
+pub struct Convolve<I> where
+    I: Iterator,
+{
+    iter: Fuse<I>,
+    kernel: Vec<I::Item>,
+    buf: VecDeque<I::Item>,
+}
+
+impl<I> Convolve<I> where
+    I: Iterator,
+{
+    /// Create a new `Convolve` iterator.
+    ///
+    /// **Panics** if `kernel` is empty.
+    pub fn new(iter: I, kernel: Vec<I::Item>) -> Self
+    {
+        assert!(!kernel.is_empty());
+        Convolve {
+            iter: iter.fuse(),
+            buf: VecDeque::with_capacity(kernel.len()),
+            kernel: kernel,
+        }
+    }
+}
+
+impl<F, I> Iterator for Convolve<I> where
+    I: Iterator<Item=F>,
+    F: Copy + Add<Output=F> + Mul<Output=F>,
+{
+    type Item = F;
+
+    fn next(&mut self) -> Option<F>
+    {
+        while self.buf.len() < self.kernel.len() {
+            match self.iter.next() {
+                Some(elt) => self.buf.push_back(elt),
+                None => return None,
+            }
+        }
+        let mut products = self.buf.iter().zip(&self.kernel).map(|(&x, &k)| x * k);
+        let dot = products.fold1(|a, b| a + b);
+        self.buf.pop_front();
+        dot
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        // the buffer holds less than a full window between iterations
+        let sh = size_hint::add_scalar(self.iter.size_hint(), self.buf.len());
+        size_hint::sub_scalar(sh, self.kernel.len() - 1)
+    }
+}
+
+/// An iterator adaptor that yields the sum of each sliding window of the
+/// base iterator, maintained as a running sum.
+///
+/// Iterator element type is `I::Item`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.window_sums()*](trait.Itertools.html#method.window_sums) for more information.
+#[derive(Clone)]
+pub struct WindowSums<I> where
+    I: Iterator,
+{
+    iter: Fuse<I>,
+    window: usize,
+    buf: VecDeque<I::Item>,
+    sum: Option<I::Item>,
+}
+
+impl<I> WindowSums<I> where
+    I: Iterator,
+{
+    /// Create a new `WindowSums` iterator.
+    ///
+    /// **Panics** if `window` is 0.
+    pub fn new(iter: I, window: usize) -> Self
+    {
+        assert!(window != 0);
+        WindowSums {
+            iter: iter.fuse(),
+            window: window,
+            buf: VecDeque::new(),
+            sum: None,
+        }
+    }
+}
+
+impl<A, I> Iterator for WindowSums<I> where
+    I: Iterator<Item=A>,
+    A: Clone + Add<Output=A> + Sub<Output=A>,
+{
+    type Item = A;
+
+    fn next(&mut self) -> Option<A>
+    {
+        let sum = match self.sum.take() {
+            None => {
+                while self.buf.len() < self.window {
+                    match self.iter.next() {
+                        Some(elt) => self.buf.push_back(elt),
+                        None => return None,
+                    }
+                }
+                self.buf.iter().cloned().fold1(|a, b| a + b)
+            }
+            Some(sum) => match self.iter.next() {
+                None => {
+                    self.sum = Some(sum);
+                    return None;
+                }
+                Some(elt) => {
+                    let leaving = self.buf.pop_front().unwrap();
+                    self.buf.push_back(elt.clone());
+                    Some(sum + elt - leaving)
+                }
+            },
+        };
+        self.sum = sum.clone();
+        sum
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        if self.sum.is_some() {
+            self.iter.size_hint()
+        } else {
+            let sh = size_hint::add_scalar(self.iter.size_hint(), self.buf.len());
+            size_hint::sub_scalar(sh, self.window - 1)
+        }
+    }
+}
+
+/// An iterator adaptor that yields each sliding window of the base iterator
+/// together with a hash of the window.
+///
+/// Iterator element type is `(u64, Vec<I::Item>)`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.with_rolling_hash()*](trait.Itertools.html#method.with_rolling_hash) for more information.
+#[derive(Clone)]
+pub struct WithRollingHash<I, F> where
+    I: Iterator,
+{
+    iter: Fuse<I>,
+    window: usize,
+    buf: VecDeque<I::Item>,
+    hash: F,
+}
+
+impl<I, F> WithRollingHash<I, F> where
+    I: Iterator,
+{
+    /// Create a new `WithRollingHash` iterator.
+    ///
+    /// **Panics** if `window` is 0.
+    pub fn new(iter: I, window: usize, hash: F) -> Self
+    {
+        assert!(window != 0);
+        WithRollingHash {
+            iter: iter.fuse(),
+            window: window,
+            buf: VecDeque::new(),
+            hash: hash,
+        }
+    }
+}
+
+impl<I, F> Iterator for WithRollingHash<I, F> where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&[I::Item]) -> u64,
+{
+    type Item = (u64, Vec<I::Item>);
+
+    fn next(&mut self) -> Option<(u64, Vec<I::Item>)>
+    {
+        if self.buf.len() == self.window {
+            self.buf.pop_front();
+        }
+        while self.buf.len() < self.window {
+            match self.iter.next() {
+                Some(elt) => self.buf.push_back(elt),
+                None => return None,
+            }
+        }
+        let window = self.buf.iter().cloned().collect::<Vec<_>>();
+        let h = (self.hash)(&window);
+        Some((h, window))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        // a full buffer is the previous window, which is not yielded again
+        let buffered = cmp::min(self.buf.len(), self.window - 1);
+        let sh = size_hint::add_scalar(self.iter.size_hint(), buffered);
+        size_hint::sub_scalar(sh, self.window - 1)
+    }
+}
+
+/// An iterator adaptor that yields the number of distinct elements in each
+/// sliding window of the base iterator.
+///
+/// Iterator element type is `usize`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.window_distinct_count()*](trait.Itertools.html#method.window_distinct_count) for more information.
+#[derive(Clone)]
+pub struct WindowDistinctCount<I> where
+    I: Iterator,
+{
+    iter: Fuse<I>,
+    window: usize,
+    buf: VecDeque<I::Item>,
+    counts: HashMap<I::Item, usize>,
+}
+
+impl<I> WindowDistinctCount<I> where
+    I: Iterator,
+    I::Item: Eq + Hash,
+{
+    /// Create a new `WindowDistinctCount` iterator.
+    ///
+    /// **Panics** if `window` is 0.
+    pub fn new(iter: I, window: usize) -> Self
+    {
+        assert!(window != 0);
+        WindowDistinctCount {
+            iter: iter.fuse(),
+            window: window,
+            buf: VecDeque::new(),
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<I> Iterator for WindowDistinctCount<I> where
+    I: Iterator,
+    I::Item: Eq + Hash + Clone,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize>
+    {
+        if self.buf.len() == self.window {
+            let leaving = self.buf.pop_front().unwrap();
+            let gone = {
+                let count = self.counts.get_mut(&leaving).unwrap();
+                *count -= 1;
+                *count == 0
+            };
+            if gone {
+                self.counts.remove(&leaving);
+            }
+        }
+        while self.buf.len() < self.window {
+            match self.iter.next() {
+                Some(elt) => {
+                    *self.counts.entry(elt.clone()).or_insert(0) += 1;
+                    self.buf.push_back(elt);
+                }
+                None => return None,
+            }
+        }
+        Some(self.counts.len())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        // a full buffer is the previous window, which is not yielded again
+        let buffered = cmp::min(self.buf.len(), self.window - 1);
+        let sh = size_hint::add_scalar(self.iter.size_hint(), buffered);
+        size_hint::sub_scalar(sh, self.window - 1)
+    }
+}
+
+/// An iterator adaptor that applies a function to each triple of adjacent
+/// elements of the base iterator.
+///
+/// Iterator element type is `B`, the return type of the function.
+///
+/// This iterator is *fused*.
+///
+/// See [*.map_triples()*](trait.Itertools.html#method.map_triples) for more information.
+#[derive(Clone)]
+pub struct MapTriples<I, F> where
+    I: Iterator,
+{
+    iter: Fuse<I>,
+    prev: Option<(I::Item, I::Item)>,
+    f: F,
+}
+
+impl<I, F> MapTriples<I, F> where
+    I: Iterator,
+{
+    /// Create a new `MapTriples` iterator.
+    pub fn new(iter: I, f: F) -> Self
+    {
+        let mut iter = iter.fuse();
+        let prev = match (iter.next(), iter.next()) {
+            (Some(a), Some(b)) => Some((a, b)),
+            _ => None,
+        };
+        MapTriples { iter: iter, prev: prev, f: f }
+    }
+}
+
+impl<B, I, F> Iterator for MapTriples<I, F> where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&I::Item, &I::Item, &I::Item) -> B,
+{
+    type Item = B;
+
+    fn next(&mut self) -> Option<B>
+    {
+        let c = match self.prev {
+            None => return None,
+            Some(_) => match self.iter.next() {
+                None => return None,
+                Some(c) => c,
+            },
+        };
+        let (a, b) = self.prev.take().unwrap();
+        let res = (self.f)(&a, &b, &c);
+        self.prev = Some((b, c));
+        Some(res)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        match self.prev {
+            None => (0, Some(0)),
+            Some(_) => self.iter.size_hint(),
+        }
+    }
+}
+
+impl<B, I, F> ExactSizeIterator for MapTriples<I, F> where
+    I: ExactSizeIterator,
+    I::Item: Clone,
+    F: FnMut(&I::Item, &I::Item, &I::Item) -> B,
+{ }
+
+/// An iterator adaptor that drops each element equal to one of the last
+/// elements it yielded.
+///
+/// Iterator element type is `I::Item`.
+///
+/// See [*.dedup_window()*](trait.Itertools.html#method.dedup_window) for more information.
+#[derive(Clone)]
+pub struct DedupWindow<I> where
+    I: Iterator,
+{
+    iter: I,
+    window: usize,
+    recent: VecDeque<I::Item>,
+    /// the elements of `recent`, which are all distinct
+    seen: HashSet<I::Item>,
+}
+
+impl<I> DedupWindow<I> where
+    I: Iterator,
+    I::Item: Eq + Hash,
+{
+    /// Create a new `DedupWindow` iterator.
+    pub fn new(iter: I, window: usize) -> Self
+    {
+        DedupWindow {
+            iter: iter,
+            window: window,
+            recent: VecDeque::new(),
+            seen: HashSet::new(),
+        }
+    }
+}
+
+impl<I> Iterator for DedupWindow<I> where
+    I: Iterator,
+    I::Item: Eq + Hash + Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item>
+    {
+        for elt in self.iter.by_ref() {
+            if self.seen.contains(&elt) {
+                continue;
+            }
+            if self.window == 0 {
+                return Some(elt);
+            }
+            if self.recent.len() == self.window {
+                let leaving = self.recent.pop_front().unwrap();
+                self.seen.remove(&leaving);
+            }
+            self.seen.insert(elt.clone());
+            self.recent.push_back(elt.clone());
+            return Some(elt);
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        let (low, hi) = self.iter.size_hint();
+        (cmp::min(low, 1), hi)
+    }
+}
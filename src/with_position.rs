@@ -0,0 +1,107 @@
+use std::iter::{Fuse, Peekable};
+
+/// An iterator adaptor that wraps each element in a [`Position`](enum.Position.html).
+///
+/// Iterator element type is `Position<I::Item>`.
+///
+/// This iterator is *fused*.
+///
+/// See [*.with_position()*](trait.Itertools.html#method.with_position) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct WithPosition<I> where
+    I: Iterator,
+{
+    handled_first: bool,
+    peekable: Peekable<Fuse<I>>,
+}
+
+impl<I> Clone for WithPosition<I> where
+    I: Iterator + Clone,
+    I::Item: Clone,
+{
+    fn clone(&self) -> Self
+    {
+        WithPosition {
+            handled_first: self.handled_first,
+            peekable: self.peekable.clone(),
+        }
+    }
+}
+
+impl<I> WithPosition<I> where
+    I: Iterator,
+{
+    /// Create a new `WithPosition` iterator.
+    pub fn new(iter: I) -> WithPosition<I>
+    {
+        WithPosition {
+            handled_first: false,
+            peekable: iter.fuse().peekable(),
+        }
+    }
+}
+
+/// A value yielded by `WithPosition`.
+/// Indicates the position of this element in the iterator results.
+///
+/// See [*.with_position()*](trait.Itertools.html#method.with_position) for more information.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Position<T> {
+    /// This is the first element.
+    First(T),
+    /// This is neither the first nor the last element.
+    Middle(T),
+    /// This is the last element.
+    Last(T),
+    /// This is the only element.
+    Only(T),
+}
+
+impl<T> Position<T> {
+    /// Return the inner value.
+    pub fn into_inner(self) -> T
+    {
+        match self {
+            Position::First(x) |
+            Position::Middle(x) |
+            Position::Last(x) |
+            Position::Only(x) => x,
+        }
+    }
+}
+
+impl<I> Iterator for WithPosition<I> where
+    I: Iterator,
+{
+    type Item = Position<I::Item>;
+
+    fn next(&mut self) -> Option<Position<I::Item>>
+    {
+        let item = match self.peekable.next() {
+            Some(item) => item,
+            None => return None,
+        };
+        let is_last = self.peekable.peek().is_none();
+        if !self.handled_first {
+            self.handled_first = true;
+            if is_last {
+                Some(Position::Only(item))
+            } else {
+                Some(Position::First(item))
+            }
+        } else if is_last {
+            Some(Position::Last(item))
+        } else {
+            Some(Position::Middle(item))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        self.peekable.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for WithPosition<I> where
+    I: ExactSizeIterator,
+{ }
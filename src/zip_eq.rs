@@ -0,0 +1,50 @@
+use super::size_hint;
+
+/// An iterator which iterates two other iterators simultaneously,
+/// checking that they have the same length.
+///
+/// See [*.zip_eq()*](trait.Itertools.html#method.zip_eq) for more information.
+#[derive(Clone)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct ZipEq<T, U> {
+    a: T,
+    b: U,
+}
+
+impl<T, U> ZipEq<T, U> where
+    T: Iterator,
+    U: Iterator,
+{
+    /// Create a new `ZipEq` iterator.
+    pub fn new(a: T, b: U) -> ZipEq<T, U>
+    {
+        ZipEq{a: a, b: b}
+    }
+}
+
+impl<T, U> Iterator for ZipEq<T, U> where
+    T: Iterator,
+    U: Iterator,
+{
+    type Item = (T::Item, U::Item);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match (self.a.next(), self.b.next()) {
+            (None, None) => None,
+            (Some(a), Some(b)) => Some((a, b)),
+            (Some(_), None) | (None, Some(_)) =>
+                panic!("itertools: .zip_eq() reached end of one iterator before the other"),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint::min(self.a.size_hint(), self.b.size_hint())
+    }
+}
+
+impl<T, U> ExactSizeIterator for ZipEq<T, U> where
+    T: ExactSizeIterator,
+    U: ExactSizeIterator,
+{ }
@@ -329,6 +329,17 @@ fn size_dedup(a: Vec<i32>) -> bool {
     correct_size_hint(a.iter().dedup())
 }
 
+#[quickcheck]
+fn equal_unique(a: Vec<i8>) -> bool {
+    let mut b = Vec::new();
+    for &x in &a {
+        if !b.contains(&x) {
+            b.push(x);
+        }
+    }
+    itertools::equal(&b, a.iter().unique())
+}
+
 #[quickcheck]
 fn size_group_by(a: Vec<i8>) -> bool {
     correct_size_hint(a.iter().group_by(|x| x.abs()))
@@ -591,4 +602,173 @@ fn exact_size_zipslices_stride(a: Vec<u8>, b: Vec<u8>, mut s1: i8, mut s2: i8) -
                                       Stride::from_slice(&b, s2 as isize)))
 }
 
+#[quickcheck]
+fn size_windowed_step(it: Iter<i16>, size: u8, step: u8) -> bool {
+    let size = size as usize % 8 + 1;
+    let step = step as usize % 8 + 1;
+    correct_size_hint(it.windowed_step(size, step))
+}
+
+#[quickcheck]
+fn size_sample_exponential(a: Vec<u8>, base: u8) -> bool {
+    let base = base as usize % 5 + 1;
+    correct_size_hint(a.iter().sample_exponential(base))
+}
+
+#[quickcheck]
+fn size_pad_to_stride(a: Iter<i16>, stride: u8) -> bool {
+    let stride = stride as usize % 8 + 1;
+    correct_size_hint(a.pad_to_stride(stride))
+}
+
+#[quickcheck]
+fn size_product_diagonal(a: Iter<u16>, b: Iter<u16>) -> bool {
+    correct_size_hint(a.product_diagonal(b))
+}
+
+#[quickcheck]
+fn equal_product_diagonal(a: Vec<u8>, b: Vec<u8>) -> bool {
+    let mut x = a.iter().cartesian_product(b.iter()).collect_vec();
+    let mut y = a.iter().product_diagonal(b.iter()).collect_vec();
+    x.sort();
+    y.sort();
+    x == y
+}
+
+#[quickcheck]
+fn interleave_rev(a: Vec<u8>, b: Vec<u8>) -> bool {
+    let mut forward = a.iter().interleave(b.iter()).collect_vec();
+    forward.reverse();
+    itertools::equal(forward, a.iter().interleave(b.iter()).rev())
+}
+
+#[quickcheck]
+fn size_palindrome(a: Vec<u8>) -> bool {
+    exact_size(a.into_iter().palindrome())
+}
+
+#[quickcheck]
+fn product_rev(a: Vec<u8>, b: Vec<u8>) -> bool {
+    let mut forward = a.iter().cartesian_product(b.iter()).collect_vec();
+    forward.reverse();
+    itertools::equal(forward, a.iter().cartesian_product(b.iter()).rev())
+}
+
+#[quickcheck]
+fn equal_kmerge(a: Vec<i16>, b: Vec<i16>, c: Vec<i16>) -> bool {
+    use itertools::kmerge;
+    let mut sa = a.clone();
+    let mut sb = b.clone();
+    let mut sc = c.clone();
+    sa.sort();
+    sb.sort();
+    sc.sort();
+    let mut merged = sa.clone();
+    merged.extend(sb.iter().cloned());
+    merged.extend(sc.iter().cloned());
+    merged.sort();
+    itertools::equal(merged.into_iter(), kmerge(vec![sa, sb, sc]))
+}
+
+#[quickcheck]
+fn size_intersperse_cycle(a: Iter<i16>, x: i16, y: i16) -> bool {
+    correct_size_hint(a.intersperse_cycle(vec![x, y]))
+}
+
+#[quickcheck]
+fn size_chunks(a: Iter<i16>, size: u8) -> bool {
+    let size = size as usize % 8 + 1;
+    correct_size_hint(a.chunks(size))
+}
+
+#[quickcheck]
+fn size_tuple_windows(a: Iter<i16>) -> bool {
+    correct_size_hint(a.tuple_windows::<(_, _, _)>())
+}
+
+#[quickcheck]
+fn equal_dedup_by(a: Vec<i32>) -> bool {
+    itertools::equal(a.iter().dedup(), a.iter().dedup_by(|x, y| x == y))
+}
+
+#[quickcheck]
+fn equal_dedup_with_count(a: Vec<i8>) -> bool {
+    let counts = a.iter().dedup_with_count().collect_vec();
+    itertools::equal(counts.iter().map(|&(_, x)| x), a.iter().dedup()) &&
+        counts.iter().map(|&(n, _)| n).fold(0, |a, b| a + b) == a.len()
+}
+
+#[quickcheck]
+fn lis_len_quadratic(a: Vec<u8>) -> bool {
+    // longest increasing subsequence ending at each index
+    let mut best = vec![1; a.len()];
+    for i in 0..a.len() {
+        for j in 0..i {
+            if a[j] < a[i] && best[j] + 1 > best[i] {
+                best[i] = best[j] + 1;
+            }
+        }
+    }
+    best.iter().cloned().max().unwrap_or(0) == a.iter().lis_len()
+}
+
+#[quickcheck]
+fn size_intersperse_with(a: Iter<i16>, x: i16) -> bool {
+    correct_size_hint(a.intersperse_with(|| x))
+}
+
+#[quickcheck]
+fn argsort_sorts(a: Vec<u8>) -> bool {
+    let order = a.iter().argsort();
+    let mut sorted = a.clone();
+    sorted.sort();
+    itertools::equal(order.iter().map(|&i| a[i]), sorted)
+}
+
+#[quickcheck]
+fn size_batching_with_size_hint(a: Iter<i16>) -> bool {
+    let pairs = itertools::Batching::with_size_hint(a, |it: &mut Iter<i16>| {
+        match (it.next(), it.next()) {
+            (Some(x), Some(y)) => Some((x, y)),
+            _ => None,
+        }
+    }, |(l, h)| (l / 2, h.map(|x| x / 2)));
+    correct_size_hint(pairs)
+}
+
+#[quickcheck]
+fn intersperse_rev(a: Vec<u8>, x: u8) -> bool {
+    let mut forward = a.iter().intersperse(&x).collect_vec();
+    forward.reverse();
+    itertools::equal(forward, a.iter().intersperse(&x).rev())
+}
+
+#[quickcheck]
+fn size_group_by_count(a: Vec<i8>) -> bool {
+    correct_size_hint(a.iter().group_by_count(|x| x.abs()))
+}
+
+#[quickcheck]
+fn size_pairwise(a: Vec<u8>) -> bool {
+    exact_size(a.into_iter().pairwise(|x, y| (x, y)))
+}
+
+#[quickcheck]
+fn size_product_partial(a: Vec<u8>, b: Vec<u8>, n: u8, m: u8) -> bool {
+    let b = b.into_iter().filter(|_| true);
+    let mut prod = a.iter().cartesian_product(b);
+    prod.dropn(n as usize);
+    for _ in 0..m {
+        prod.next_back();
+    }
+    let (low, high) = prod.size_hint();
+    let len = prod.count();
+    low <= len && high.map_or(true, |h| len <= h)
+}
+
+#[quickcheck]
+fn size_multi_product(a: Vec<u8>, b: Vec<u8>, c: Vec<u8>) -> bool {
+    correct_size_hint(itertools::multi_cartesian_product(vec![a, b, c]))
+}
+
 }
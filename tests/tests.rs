@@ -52,6 +52,66 @@ fn product_temporary() {
 }
 
 
+#[test]
+fn cartesian_product() {
+    let it = (0..2).cartesian_product(vec!['a', 'b']);
+    it::assert_equal(it, it::Product::new(0..2, vec!['a', 'b'].into_iter()));
+    assert_eq!((0..0).cartesian_product(0..3).next(), None);
+
+    let it = (0..2).cartesian_product(0..3);
+    assert_eq!(it.size_hint(), (6, Some(6)));
+    it::assert_equal(it, iproduct!(0..2, 0..3));
+    it::assert_equal((0..2).cartesian_product(5..7), vec![(0, 5), (0, 6), (1, 5), (1, 6)]);
+}
+
+#[test]
+fn product_double_ended() {
+    let mut prod = it::Product::new(0..2, 0..2);
+    it::assert_equal(prod.by_ref().rev(), vec![(1, 1), (1, 0), (0, 1), (0, 0)]);
+    assert_eq!(prod.next(), None);
+
+    let mut prod = (0..3).cartesian_product(0..2);
+    assert_eq!(prod.next_back(), Some((2, 1)));
+    assert_eq!(prod.next(), Some((0, 0)));
+    assert_eq!(prod.size_hint(), (4, Some(4)));
+    assert_eq!(prod.next_back(), Some((2, 0)));
+    assert_eq!(prod.next_back(), Some((1, 1)));
+    assert_eq!(prod.next(), Some((0, 1)));
+    assert_eq!(prod.next(), Some((1, 0)));
+    assert_eq!(prod.next(), None);
+    assert_eq!(prod.next_back(), None);
+
+    let mut forward = (0..3).cartesian_product("ab".chars()).collect_vec();
+    forward.reverse();
+    it::assert_equal((0..3).cartesian_product("ab".chars()).rev(), forward);
+}
+
+#[test]
+fn product_size_hint_overflow() {
+    use std::usize;
+    // the lower bound never exceeds the true length, which is larger than usize::MAX here
+    let prod = (0..usize::MAX).cartesian_product(0..3);
+    assert_eq!(prod.size_hint(), (usize::MAX, None));
+
+    let mut prod = (0..usize::MAX).cartesian_product(0..0);
+    assert_eq!(prod.size_hint(), (0, Some(0)));
+    assert_eq!(prod.next(), None);
+
+    let mut prod = (0..2).cartesian_product(0..usize::MAX / 2 + 1);
+    assert_eq!(prod.size_hint(), (usize::MAX, None));
+    assert_eq!(prod.next(), Some((0, 0)));
+    assert_eq!(prod.size_hint(), (usize::MAX, Some(usize::MAX)));
+}
+
+#[test]
+fn product_size_hint_partial() {
+    // the rest of the current row counts towards the upper bound as well
+    let mut prod = (0..2).cartesian_product((0..4).filter(|_| true));
+    assert_eq!(prod.next(), Some((0, 0)));
+    assert_eq!(prod.size_hint(), (0, Some(7)));
+    assert_eq!(prod.count(), 7);
+}
+
 #[test]
 fn izip_macro() {
     let mut zip = izip!(0..3, 0..2, 0..2i8);
@@ -82,6 +142,17 @@ fn izip3() {
     }
 }
 
+#[test]
+fn zip_exact_size() {
+    let xs = [1, 2, 3, 4];
+    let mut zip = Zip::new((0..5, xs.iter(), vec!['a', 'b', 'c']));
+    assert_eq!(zip.size_hint(), (3, Some(3)));
+    assert_eq!(zip.len(), 3);
+    zip.next();
+    assert_eq!(zip.len(), 2);
+    it::assert_equal(zip, vec![(1, &2, 'b'), (2, &3, 'c')]);
+}
+
 #[test]
 fn write_to() {
     let xs = [7, 9, 8];
@@ -105,9 +176,50 @@ fn interleave() {
 
     let rs = [7u8, 2, 9, 77, 8, 10];
     let it = Interleave::new(ys.iter(), zs.iter());
+    assert_eq!(it.size_hint(), (6, Some(6)));
     it::assert_equal(it, rs.iter());
 }
 
+#[test]
+fn interleave_size_hint() {
+    let xs = vec![1, 2, 3, 4, 5];
+    let ys = vec![10, 20];
+    let mut manual = Vec::new();
+    for i in 0..5 {
+        manual.push(xs[i]);
+        if i < ys.len() {
+            manual.push(ys[i]);
+        }
+    }
+    let mut it = xs.iter().cloned().interleave(ys.iter().cloned());
+    for remaining in (0..manual.len() + 1).rev() {
+        assert_eq!(it.size_hint(), (remaining, Some(remaining)));
+        assert_eq!(it.next(), manual.get(manual.len() - remaining).cloned());
+    }
+    assert_eq!((0..).interleave(0..3).size_hint(), (usize::max_value(), None));
+}
+
+#[test]
+fn interleave_double_ended() {
+    let xs = [1, 3, 5, 7, 8];
+    let ys = [2, 4];
+    let mut it = xs.iter().cloned().interleave(ys.iter().cloned());
+    assert_eq!(it.next_back(), Some(8));
+    assert_eq!(it.next(), Some(1));
+    assert_eq!(it.next_back(), Some(7));
+    assert_eq!(it.next_back(), Some(5));
+    assert_eq!(it.next(), Some(2));
+    assert_eq!(it.next_back(), Some(4));
+    assert_eq!(it.next(), Some(3));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next_back(), None);
+
+    let it = (0..3).interleave(10..13);
+    it::assert_equal(it.rev(), vec![12, 2, 11, 1, 10, 0]);
+    let it = (0..2).interleave(10..15);
+    it::assert_equal(it.rev(), vec![14, 13, 12, 11, 1, 10, 0]);
+}
+
 #[test]
 fn interleave_shortest() {
     let v0: Vec<i32> = vec![0, 2, 4];
@@ -131,6 +243,11 @@ fn interleave_shortest() {
     let i1 = ::std::iter::repeat(1);
     let it = v0.into_iter().interleave_shortest(i1);
     assert_eq!(it.size_hint(), (6, Some(6)));
+
+    // iteration stops when the iterator whose turn it is runs out
+    it::assert_equal([1, 2, 3].iter().interleave_shortest(&[10, 20]), &[1, 10, 2, 20, 3]);
+    it::assert_equal([1, 2].iter().interleave_shortest(&[10, 20]), &[1, 10, 2, 20]);
+    it::assert_equal([1, 2].iter().interleave_shortest(&[10, 20, 30]), &[1, 10, 2, 20]);
 }
 
 #[test]
@@ -175,6 +292,29 @@ fn intersperse() {
     assert!(it.next() == None);
 }
 
+#[test]
+fn intersperse_rev() {
+    let xs = [1, 2, 3];
+    let mut forward = xs.iter().intersperse(&0).collect::<Vec<_>>();
+    forward.reverse();
+    it::assert_equal(xs.iter().intersperse(&0).rev(), forward);
+
+    // from both ends
+    let mut it = xs.iter().cloned().intersperse(0);
+    assert_eq!(it.next_back(), Some(3));
+    assert_eq!(it.size_hint(), (4, Some(4)));
+    assert_eq!(it.next(), Some(1));
+    assert_eq!(it.next_back(), Some(0));
+    assert_eq!(it.next_back(), Some(2));
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next_back(), None);
+
+    let mut it = xs[..1].iter().cloned().intersperse(0);
+    assert_eq!(it.next_back(), Some(1));
+    assert_eq!(it.next(), None);
+}
+
 #[test]
 fn linspace() {
     let iter = it::linspace::<f32>(0., 2., 3);
@@ -214,11 +354,62 @@ fn dedup() {
     it::assert_equal(ys.iter(), xs.iter().dedup());
 }
 
+#[test]
+fn dedup_by() {
+    let xs = ["a", "bb", "cc", "d"];
+    let it = xs.iter().dedup_by(|x, y| x.len() == y.len());
+    it::assert_equal(it, vec![&"a", &"bb", &"d"]);
+
+    // the first element of each section is compared to the following ones
+    let it = (0..10).dedup_by(|&x, &y| y - x < 3);
+    it::assert_equal(it, vec![0, 3, 6, 9]);
+
+    // (key, value) pairs compared on the key only
+    let pairs = [(1, 'a'), (1, 'b'), (2, 'c'), (2, 'd'), (1, 'e')];
+    let it = pairs.iter().cloned().dedup_by(|x, y| x.0 == y.0);
+    it::assert_equal(it, vec![(1, 'a'), (2, 'c'), (1, 'e')]);
+}
+
+#[test]
+fn dedup_with_count() {
+    let xs = [1, 1, 1, 2, 3, 3];
+    let it = xs.iter().cloned().dedup_with_count();
+    it::assert_equal(it, vec![(3, 1), (1, 2), (2, 3)]);
+    let it = "aabccc".chars().dedup_with_count();
+    it::assert_equal(it, vec![(2, 'a'), (1, 'b'), (3, 'c')]);
+    assert_eq!((0..0).dedup_with_count().next(), None);
+}
+
+#[test]
+fn dedup_double_ended() {
+    let xs = [1, 1, 2, 2, 3, 3];
+    let mut it = xs.iter().cloned().dedup();
+    assert_eq!(it.next(), Some(1));
+    assert_eq!(it.next_back(), Some(3));
+    assert_eq!(it.next(), Some(2));
+    assert_eq!(it.next_back(), None);
+    assert_eq!(it.next(), None);
+
+    let xs = [0, 1, 1, 1, 2, 1, 3, 3];
+    it::assert_equal(xs.iter().dedup().rev(), vec![&3, &1, &2, &1, &0]);
+    let mut it = xs.iter().dedup();
+    assert_eq!(it.next_back(), Some(&3));
+    assert_eq!(it.next_back(), Some(&1));
+    it::assert_equal(it, vec![&0, &1, &2]);
+}
+
 #[test]
 fn unique_by() {
     let xs = ["aaa", "bbbbb", "aa", "ccc", "bbbb", "aaaaa", "cccc"];
     let ys = ["aaa", "bbbbb", "ccc"];
     it::assert_equal(ys.iter(), xs.iter().unique_by(|x| x[..2].to_string()));
+
+    let it = xs.iter().cloned().unique_by(|s: &&str| s.len());
+    it::assert_equal(it, vec!["aaa", "bbbbb", "aa", "bbbb"]);
+
+    let fruits = ["apple", "avocado", "banana", "cherry"];
+    let it = fruits.iter().unique_by(|s| s.chars().next());
+    it::assert_equal(it, &["apple", "banana", "cherry"]);
 }
 
 #[test]
@@ -229,6 +420,12 @@ fn unique() {
     let xs = [0, 1];
     let ys = [0, 1];
     it::assert_equal(ys.iter(), xs.iter().unique());
+
+    let mut it = vec![1, 2, 1, 3, 2, 4].into_iter().unique();
+    assert_eq!(it.size_hint(), (1, Some(6)));
+    assert_eq!(it.next(), Some(1));
+    assert_eq!(it.size_hint(), (0, Some(5)));
+    it::assert_equal(it, vec![2, 3, 4]);
 }
 
 #[test]
@@ -247,6 +444,19 @@ fn batching() {
                }
            });
     it::assert_equal(pit, ys.iter().cloned());
+
+    // the same, reporting a size hint
+    let pit = it::Batching::with_size_hint(xs.iter().cloned(), |it| {
+               match it.next() {
+                   None => None,
+                   Some(x) => match it.next() {
+                       None => None,
+                       Some(y) => Some((x, y)),
+                   }
+               }
+           }, |(l, h)| (l / 2, h.map(|x| x / 2)));
+    assert_eq!(pit.size_hint(), (2, Some(2)));
+    it::assert_equal(pit, ys.iter().cloned());
 }
 
 #[test]
@@ -259,6 +469,17 @@ fn group_by() {
     it::assert_equal(gb, ans.into_iter());
 }
 
+#[test]
+fn group_by_count() {
+    let it = [1, 1, 1, 2, 3, 3].iter().group_by_count(|&&x| x);
+    it::assert_equal(it, vec![(1, 3), (2, 1), (3, 2)]);
+
+    let xs = [0, 1, 1, 1, 2, 1, 3, 3];
+    let counts = xs.iter().group_by(|&&x| x).map(|(k, v)| (k, v.len()));
+    it::assert_equal(xs.iter().group_by_count(|&&x| x), counts);
+    assert_eq!((0..0).group_by_count(|&x| x).next(), None);
+}
+
 #[test]
 fn put_back() {
     let xs = [0, 1, 1, 1, 2, 1, 3, 3];
@@ -269,6 +490,38 @@ fn put_back() {
     it::assert_equal(pb, xs.iter().cloned());
 }
 
+#[test]
+fn put_back_double_ended() {
+    let mut pb = it::PutBack::new(1..4);
+    pb.put_back(0);
+    assert_eq!(pb.next_back(), Some(3));
+    assert_eq!(pb.next_back(), Some(2));
+    it::assert_equal(pb.by_ref(), vec![0, 1]);
+    assert_eq!(pb.next_back(), None);
+
+    let mut pb = it::PutBack::new(0..1);
+    pb.put_back(7);
+    it::assert_equal(pb.rev(), vec![0, 7]);
+}
+
+#[test]
+fn put_back_n_lifo() {
+    let mut pb = it::PutBackN::new(0..2);
+    pb.put_back(10);
+    pb.put_back(11);
+    pb.put_back(12);
+    assert_eq!(pb.size_hint(), (5, Some(5)));
+    it::assert_equal(pb, vec![12, 11, 10, 0, 1]);
+
+    // tokens can be unread after the end of the stream
+    let mut pb = it::PutBackN::new(0..2);
+    assert_eq!(pb.by_ref().count(), 2);
+    pb.put_back(1);
+    pb.put_back(0);
+    assert_eq!(pb.size_hint(), (2, Some(2)));
+    it::assert_equal(pb, 0..2);
+}
+
 #[test]
 fn put_back_n() {
     let xs = [0, 1, 1, 1, 2, 1, 3, 3];
@@ -340,6 +593,16 @@ fn step() {
     it::assert_equal((0..10).step(1), (0..10));
     it::assert_equal((0..10).step(2), (0..10).filter(|x: &i32| *x % 2 == 0));
     it::assert_equal((0..10).step(10), 0..1);
+
+    let it = (0..10).step(3);
+    assert_eq!(it.len(), 4);
+    it::assert_equal(it, vec![0, 3, 6, 9]);
+}
+
+#[test]
+#[should_panic]
+fn step_zero() {
+    (0..10).step(0);
 }
 
 #[test]
@@ -377,6 +640,26 @@ fn trait_pointers() {
 #[test]
 fn merge() {
     it::assert_equal((0..10).step(2).merge((1..10).step(2)), (0..10));
+
+    // the size hint is the sum of both sides
+    let it = (0..3).merge(5..7);
+    assert_eq!(it.size_hint(), (5, Some(5)));
+    it::assert_equal(it, vec![0, 1, 2, 5, 6]);
+    it::assert_equal((0..0).merge(vec![2, 4, 8]), vec![2, 4, 8]);
+    it::assert_equal(vec![1, 9].into_iter().merge(0..0), vec![1, 9]);
+
+    // ties keep the left element first
+    struct Key(i32, char);
+    impl PartialEq for Key {
+        fn eq(&self, other: &Key) -> bool { self.0 == other.0 }
+    }
+    impl PartialOrd for Key {
+        fn partial_cmp(&self, other: &Key) -> Option<std::cmp::Ordering> { self.0.partial_cmp(&other.0) }
+    }
+    let a = vec![Key(1, 'a'), Key(2, 'a')];
+    let b = vec![Key(1, 'b'), Key(2, 'b')];
+    let it = a.into_iter().merge(b);
+    it::assert_equal(it.map(|x| x.1), "abab".chars());
 }
 
 #[test]
@@ -386,6 +669,10 @@ fn merge_by() {
     let expected = vec![(1, "hello"), (2, "foo"), (3, "world"), (4, "bar"), (5, "!"), (6, "baz")];
     let results = odd.iter().merge_by(even.iter(), |a, b| a.0 <= b.0);
     it::assert_equal(results, expected.iter());
+
+    // descending order
+    let results = vec![9, 5, 1].into_iter().merge_by(vec![8, 6, 2], |a, b| a > b);
+    it::assert_equal(results, vec![9, 8, 6, 5, 2, 1]);
 }
 
 #[test]
@@ -449,6 +736,32 @@ fn multipeek() {
 
 }
 
+#[test]
+fn multipeek_ahead() {
+    let mut it = (0..5).multipeek();
+    assert_eq!(it.peek(), Some(&0));
+    assert_eq!(it.peek(), Some(&1));
+    assert_eq!(it.peek(), Some(&2));
+    // peeking does not consume
+    assert_eq!(it.len(), 5);
+    it::assert_equal(it, 0..5);
+}
+
+#[test]
+fn multipeek_reset() {
+    let mut it = (0..5).multipeek();
+    assert_eq!(it.peek(), Some(&0));
+    assert_eq!(it.peek(), Some(&1));
+    it.reset_peek();
+    assert_eq!(it.peek(), Some(&0));
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.peek(), Some(&1));
+    assert_eq!(it.peek(), Some(&2));
+    it.reset_peek();
+    assert_eq!(it.peek(), Some(&1));
+    it::assert_equal(it, 1..5);
+}
+
 #[test]
 fn repeatn() {
     let s = "α";
@@ -459,6 +772,11 @@ fn repeatn() {
     assert_eq!(it.next(), Some(s));
     assert_eq!(it.next(), None);
     assert_eq!(it.next(), None);
+
+    let it = it::RepeatN::new(vec![1], 2);
+    it::assert_equal(it.clone().rev(), vec![vec![1], vec![1]]);
+    assert_eq!(it.len(), 2);
+    assert_eq!(it::RepeatN::new(0, 0).next_back(), None);
 }
 
 #[test]
@@ -709,6 +1027,29 @@ fn group_by_lazy() {
     }
 }
 
+#[test]
+fn group_by_lazy_sum() {
+    let data = [1, 1, 2, 2, 2, 3];
+    let sums = data.iter().cloned().group_by_lazy(|&x| x).into_iter()
+                   .map(|(k, group)| (k, group.fold(0, |a, b| a + b)))
+                   .collect_vec();
+    assert_eq!(sums, vec![(1, 2), (2, 6), (3, 3)]);
+}
+
+#[test]
+fn group_by_lazy_partial() {
+    // a group that is not consumed to the end still stops at its key boundary
+    let data = [1, 1, 1, 2, 2, 3];
+    let mut firsts = Vec::new();
+    let mut total = 0;
+    for (key, mut group) in &data.iter().group_by_lazy(|&&x| x) {
+        firsts.push((key, group.next()));
+        total += group.take(1).fold(0, |a, b| a + b);
+    }
+    assert_eq!(firsts, vec![(1, Some(&1)), (2, Some(&2)), (3, Some(&3))]);
+    assert_eq!(total, 1 + 2);
+}
+
 #[test]
 fn group_by_lazy_2() {
     let data = vec![0, 1];
@@ -793,3 +1134,808 @@ fn chunks_lazy() {
         }
     }
 }
+
+#[test]
+fn zip_all() {
+    assert!([1, 2, 3].iter().zip_all(&[2, 3, 4], |a, b| *b == *a + 1));
+    assert!(![1, 2, 3].iter().zip_all(&[2, 0, 4], |a, b| *b == *a + 1));
+    // trailing elements of the longer iterator are ignored
+    assert!((0..10).zip_all(0..3, |a, b| a == b));
+    assert!((0..0).zip_all(0..3, |_, _| false));
+}
+
+#[test]
+fn sorted_unique() {
+    assert_eq!([3, 1, 2, 3, 1].iter().cloned().sorted_unique(), vec![1, 2, 3]);
+    assert_eq!((0..0).sorted_unique(), vec![]);
+}
+
+#[test]
+fn surround() {
+    let it = [1, 2].iter().cloned().surround(0, 9);
+    assert_eq!(it.size_hint(), (4, Some(4)));
+    it::assert_equal(it, vec![0, 1, 2, 9]);
+    it::assert_equal((0..0).surround(0, 9), vec![0, 9]);
+}
+
+#[test]
+fn windowed_step() {
+    use std::usize;
+    let it = (0..6).windowed_step(3, 2);
+    assert_eq!(it.size_hint(), (2, Some(2)));
+    it::assert_equal(it, vec![vec![0, 1, 2], vec![2, 3, 4]]);
+    it::assert_equal((0..6).windowed_step(2, 2), vec![vec![0, 1], vec![2, 3], vec![4, 5]]);
+    it::assert_equal((0..7).windowed_step(2, 3), vec![vec![0, 1], vec![3, 4]]);
+    it::assert_equal((0..2).windowed_step(3, 1), Vec::<Vec<i32>>::new());
+    assert_eq!((0..3).windowed_step(usize::MAX, 1).next(), None);
+}
+
+#[test]
+fn running_gcd() {
+    use std::i32;
+    it::assert_equal([12, 18, 6].iter().cloned().running_gcd(), vec![12, 6, 6]);
+    it::assert_equal([-4i32, 6].iter().cloned().running_gcd(), vec![4, 2]);
+    it::assert_equal([0u8, 0, 9].iter().cloned().running_gcd(), vec![0, 0, 9]);
+    it::assert_equal([i32::MIN, -1].iter().cloned().running_gcd(), vec![i32::MIN, 1]);
+    it::assert_equal([i32::MIN, i32::MIN].iter().cloned().running_gcd(), vec![i32::MIN, i32::MIN]);
+    it::assert_equal([2u32, 3, 4, 0].iter().cloned().running_lcm(), vec![2, 6, 12, 0]);
+    it::assert_equal([i32::MIN, -1].iter().cloned().running_lcm(), vec![i32::MIN, i32::MIN]);
+}
+
+#[test]
+fn local_maxima() {
+    let peaks = [1, 3, 2, 5, 4].iter().local_maxima()
+                               .filter(|&(is_peak, _)| is_peak)
+                               .map(|(_, x)| *x)
+                               .collect_vec();
+    assert_eq!(peaks, vec![3, 5]);
+    // equal neighbors are not peaks, nor are the ends
+    it::assert_equal([5, 2, 2, 5].iter().local_maxima().map(|t| t.0),
+                     vec![false, false, false, false]);
+    it::assert_equal([1].iter().local_maxima(), vec![(false, &1)]);
+}
+
+#[test]
+fn merge_by_key() {
+    let a = vec![(0, "start"), (3, "tick"), (3, "tock"), (9, "end")];
+    let b = vec![(1, "alpha"), (3, "beta"), (10, "gamma")];
+    let it = a.into_iter().merge_by_key(b, |&(time, _)| time);
+    it::assert_equal(it, vec![(0, "start"), (1, "alpha"), (3, "tick"), (3, "tock"),
+                              (3, "beta"), (9, "end"), (10, "gamma")]);
+}
+
+#[test]
+fn running_counts() {
+    let xs = ["a", "b", "a", "a"];
+    it::assert_equal(xs.iter().running_counts(|s| **s).map(|t| t.1),
+                     vec![1, 1, 2, 3]);
+    let it = (0..10).running_counts(|x| x % 3);
+    assert_eq!(it.size_hint(), (10, Some(10)));
+    assert_eq!(it.last(), Some((9, 4)));
+}
+
+#[test]
+fn zigzag_chunks() {
+    let it = (1..8).zigzag_chunks(3);
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    it::assert_equal(it, vec![vec![1, 2, 3], vec![6, 5, 4], vec![7]]);
+    it::assert_equal((1..6).zigzag_chunks(2), vec![vec![1, 2], vec![4, 3], vec![5]]);
+    it::assert_equal((1..5).zigzag_chunks(3), vec![vec![1, 2, 3], vec![4]]);
+    assert_eq!((0..0).zigzag_chunks(2).next(), None);
+}
+
+#[test]
+fn gaps() {
+    let it = [1, 4, 5, 10].iter().cloned().gaps();
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    it::assert_equal(it, vec![3, 1, 5]);
+    it::assert_equal([0.5, 2.0].iter().cloned().gaps(), vec![1.5]);
+    assert_eq!([1].iter().cloned().gaps().next(), None);
+    assert_eq!((0..0).gaps().next(), None);
+}
+
+#[test]
+fn interpolate() {
+    let it = vec![0.0f64, 1.0].into_iter().interpolate(1);
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    it::assert_equal(it, vec![0.0, 0.5, 1.0]);
+
+    let it = vec![0.0f32, 4.0, 0.0].into_iter().interpolate(3);
+    assert_eq!(it.size_hint(), (9, Some(9)));
+    it::assert_equal(it, vec![0., 1., 2., 3., 4., 3., 2., 1., 0.]);
+
+    it::assert_equal(vec![2.0f64].into_iter().interpolate(5), vec![2.0]);
+    it::assert_equal(vec![0.0f64, 1.0].into_iter().interpolate(0), vec![0.0, 1.0]);
+}
+
+#[test]
+fn frames() {
+    let xs = [1, 2, 0, 3, 0, 4];
+    it::assert_equal(xs.iter().cloned().frames(|x| *x == 0),
+                     vec![vec![1, 2], vec![3], vec![4]]);
+    let xs = [0, 1, 0, 0];
+    it::assert_equal(xs.iter().cloned().frames(|x| *x == 0),
+                     vec![vec![], vec![1], vec![]]);
+    assert_eq!((0..0).frames(|x| *x == 0).next(), None);
+}
+
+#[test]
+fn prefix_suffix_max() {
+    let (prefix, suffix) = [2, 1, 3, 2].iter().cloned().prefix_suffix_max();
+    assert_eq!(prefix, vec![2, 2, 3, 3]);
+    assert_eq!(suffix, vec![3, 3, 3, 2]);
+    let (prefix, suffix) = (0..0).prefix_suffix_max();
+    assert!(prefix.is_empty() && suffix.is_empty());
+}
+
+#[test]
+fn fill_failing() {
+    let it = [1, -2, 3, -4].iter().cloned().fill_failing(|x| *x >= 0, 0);
+    assert_eq!(it.len(), 4);
+    it::assert_equal(it, vec![1, 0, 3, 0]);
+}
+
+#[test]
+fn product_slices() {
+    // NoClone elements prove that nothing is cloned
+    #[derive(Debug, PartialEq)]
+    struct NoClone(u8);
+
+    let xs = [NoClone(0), NoClone(1), NoClone(2)];
+    let ys = ['a', 'b'];
+    let mut it = it::product_slices(&xs, &ys);
+    assert_eq!(it.size_hint(), (6, Some(6)));
+    assert_eq!(it.next(), Some((&xs[0], &'a')));
+    assert_eq!(it.size_hint(), (5, Some(5)));
+    it::assert_equal(it, vec![(&xs[0], &'b'), (&xs[1], &'a'), (&xs[1], &'b'),
+                              (&xs[2], &'a'), (&xs[2], &'b')]);
+
+    let empty: [char; 0] = [];
+    assert_eq!(it::product_slices(&xs, &empty).next(), None);
+}
+
+#[test]
+fn count_distinct() {
+    assert_eq!([1, 2, 2, 3, 1].iter().count_distinct(), 3);
+    assert_eq!("abracadabra".chars().count_distinct(), 5);
+    assert_eq!((0..0).count_distinct(), 0);
+}
+
+#[test]
+fn flatten_marked() {
+    let xs = ['a', 'a', 'b', 'a'];
+    let starts = xs.iter().flatten_marked(|c| **c)
+                   .enumerate()
+                   .filter(|&(_, (is_start, _))| is_start)
+                   .map(|(i, _)| i)
+                   .collect_vec();
+    assert_eq!(starts, vec![0, 2, 3]);
+
+    // reconstruct the groups of group_by from the flat stream
+    let xs = [1, 3, -2, -2, 1, 0, 1, 2];
+    let mut groups: Vec<Vec<i32>> = Vec::new();
+    for (is_start, &x) in xs.iter().flatten_marked(|x| **x >= 0) {
+        if is_start {
+            groups.push(Vec::new());
+        }
+        groups.last_mut().unwrap().push(x);
+    }
+    it::assert_equal(groups, xs.iter().cloned().group_by(|x| *x >= 0).map(|(_, g)| g));
+}
+
+#[test]
+fn convolve() {
+    let it = vec![1.0, 2.0, 3.0, 4.0].into_iter().convolve(vec![0.5, 0.5]);
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    it::assert_equal(it, vec![1.5, 2.5, 3.5]);
+    it::assert_equal((0..5).convolve(vec![1, -1, 2]), vec![3, 5, 7]);
+    assert_eq!((0..2).convolve(vec![1, 1, 1]).next(), None);
+}
+
+#[test]
+fn greedy_partition() {
+    let parts = [4, 3, 2, 1].iter().cloned().greedy_partition(2, |&x| x);
+    let totals = parts.iter().map(|p| p.iter().fold(0, |a, b| a + b)).collect_vec();
+    assert_eq!(totals, vec![5, 5]);
+
+    let parts = (0..3).greedy_partition(5, |_| 1);
+    assert_eq!(parts, vec![vec![0], vec![1], vec![2], vec![], vec![]]);
+}
+
+#[test]
+fn circular_pairs() {
+    let it = ["a", "b", "c"].iter().circular_pairs();
+    assert_eq!(it.len(), 3);
+    it::assert_equal(it, vec![(&"a", &"b"), (&"b", &"c"), (&"c", &"a")]);
+    it::assert_equal((0..1).circular_pairs(), vec![(0, 0)]);
+    assert_eq!((0..0).circular_pairs().next(), None);
+}
+
+#[test]
+fn collect_vec_exact() {
+    use std::usize;
+    let xs = [1, 2, 3, 4, 5];
+    let v = xs.iter().map(|x| x * 2).collect_vec_exact();
+    assert_eq!(v, vec![2, 4, 6, 8, 10]);
+    assert_eq!(v.capacity(), v.len());
+
+    let v = (0..).take_while(|&x| x < 3).collect_vec_exact();
+    assert_eq!(v, vec![0, 1, 2]);
+
+    let v = (0..usize::MAX).take_while(|_| false).collect_vec_exact();
+    assert_eq!(v, vec![]);
+}
+
+#[test]
+fn sample_exponential() {
+    // indices are the partial sums of 1, 2, 4, 8, ..
+    let it = (0..16).sample_exponential(2);
+    assert_eq!(it.size_hint(), (5, Some(5)));
+    it::assert_equal(it, vec![0, 1, 3, 7, 15]);
+    it::assert_equal((0..15).sample_exponential(2), vec![0, 1, 3, 7]);
+    it::assert_equal((0..20).sample_exponential(3), vec![0, 1, 4, 13]);
+    it::assert_equal((0..4).sample_exponential(1), 0..4);
+
+    let mut it = (0..100).sample_exponential(10);
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.size_hint(), (2, Some(2)));
+    it::assert_equal(it, vec![1, 11]);
+}
+
+#[test]
+fn merge_runs() {
+    let xs = [('a', 1), ('a', 2), ('b', 3)];
+    let it = xs.iter().cloned().merge_runs(|x| x.0, |x, y| (x.0, x.1 + y.1));
+    it::assert_equal(it, vec![('a', 3), ('b', 3)]);
+
+    let it = (0..10).merge_runs(|x| x / 4, |x, y| x * 10 + y);
+    it::assert_equal(it, vec![123, 4567, 89]);
+}
+
+#[test]
+fn window_sums() {
+    use std::usize;
+    let it = [1, 2, 3, 4].iter().cloned().window_sums(2);
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    it::assert_equal(it, vec![3, 5, 7]);
+    it::assert_equal((0..6).window_sums(3), vec![3, 6, 9, 12]);
+    it::assert_equal((0..3).window_sums(1), 0..3);
+    assert_eq!((0..2).window_sums(3).next(), None);
+    assert_eq!((0..3).window_sums(usize::MAX).next(), None);
+}
+
+#[test]
+fn deinterleave() {
+    let parts = ["a", "b", "c", "d", "e"].iter().cloned().deinterleave(2);
+    assert_eq!(parts, vec![vec!["a", "c", "e"], vec!["b", "d"]]);
+
+    let parts = (0..7).deinterleave(3);
+    assert_eq!(parts, vec![vec![0, 3, 6], vec![1, 4], vec![2, 5]]);
+
+    // deinterleave is the inverse of interleave
+    let parts = (0..3).interleave(10..13).deinterleave(2);
+    assert_eq!(parts, vec![vec![0, 1, 2], vec![10, 11, 12]]);
+}
+
+#[test]
+fn with_rolling_hash() {
+    use std::usize;
+    let xs = [1u64, 2, 3, 4, 5];
+    let it = xs.iter().cloned().with_rolling_hash(3, |w| w.iter().fold(0, |a, &b| a + b));
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    for (h, window) in it {
+        assert_eq!(window.len(), 3);
+        assert_eq!(h, window.iter().fold(0, |a, &b| a + b));
+    }
+    let hashes = xs.iter().cloned().with_rolling_hash(3, |w| w.iter().fold(0, |a, &b| a + b));
+    let mut hashes = hashes.map(|(h, _)| h);
+    assert_eq!(hashes.next(), Some(6));
+    assert_eq!(hashes.size_hint(), (2, Some(2)));
+    it::assert_equal(hashes, vec![9, 12]);
+    assert_eq!((0..2).with_rolling_hash(3, |_| 0).next(), None);
+    assert_eq!((0..3).with_rolling_hash(usize::MAX, |_| 0).next(), None);
+}
+
+#[test]
+fn zip_shift() {
+    use std::usize;
+    let it = [1, 2, 3, 4].iter().cloned().zip_shift(1);
+    assert_eq!(it.len(), 3);
+    it::assert_equal(it, vec![(1, 2), (2, 3), (3, 4)]);
+
+    it::assert_equal((0..3).zip_shift(0), vec![(0, 0), (1, 1), (2, 2)]);
+    assert_eq!((0..3).zip_shift(3).next(), None);
+    assert_eq!((0..3).zip_shift(5).size_hint(), (0, Some(0)));
+    assert_eq!((0..3).zip_shift(usize::MAX).count(), 0);
+}
+
+#[test]
+fn batch_until() {
+    let it = [2, 2, 2, 10, 1].iter().cloned()
+                 .batch_until(|batch| batch.iter().fold(0, |a, b| a + b) > 5);
+    it::assert_equal(it, vec![vec![2, 2, 2], vec![10], vec![1]]);
+
+    let mut calls = 0;
+    let it = (0..4).batch_until(|_| { calls += 1; false });
+    it::assert_equal(it, vec![vec![0, 1, 2, 3]]);
+    assert_eq!(calls, 4);
+    assert_eq!((0..0).batch_until(|_| true).next(), None);
+}
+
+#[test]
+fn ungroup() {
+    let groups = vec![('a', vec![1, 2]), ('b', vec![3])];
+    it::assert_equal(groups.into_iter().ungroup(), vec![('a', 1), ('a', 2), ('b', 3)]);
+
+    let groups = vec![(0, vec![]), (1, vec![10, 11, 12]), (2, vec![])];
+    let mut it = groups.into_iter().ungroup();
+    assert_eq!(it.next(), Some((1, 10)));
+    assert_eq!(it.size_hint(), (2, None));
+    it::assert_equal(it, vec![(1, 11), (1, 12)]);
+
+    // round trip through group_by
+    let xs = [1, 1, 2, 3, 3, 3];
+    let it = xs.iter().cloned().group_by(|&x| x).ungroup().map(|(_, x)| x);
+    it::assert_equal(it, xs.iter().cloned());
+}
+
+#[test]
+fn cumulative() {
+    let it = [1, 2, 3, 4].iter().cloned().cumulative();
+    assert_eq!(it.len(), 4);
+    it::assert_equal(it, vec![1, 3, 6, 10]);
+    it::assert_equal(vec![0, 5, 0].into_iter().cumulative(), vec![0, 5, 5]);
+}
+
+#[test]
+#[should_panic]
+fn cumulative_overflow() {
+    vec![usize::max_value(), 1].into_iter().cumulative().count();
+}
+
+#[test]
+fn transitions() {
+    let it = ["a", "b", "a", "b", "c"].iter().cloned().transitions();
+    it::assert_equal(it, vec![("a", "b"), ("b", "a"), ("b", "c")]);
+    it::assert_equal([1, 1, 1].iter().transitions(), vec![(&1, &1)]);
+    assert_eq!((0..1).transitions().next(), None);
+}
+
+#[test]
+fn pad_to_stride() {
+    let it = ["a", "b", "c"].iter().cloned().pad_to_stride(2);
+    assert_eq!(it.size_hint(), (4, Some(4)));
+    it::assert_equal(it, vec![Some("a"), Some("b"), Some("c"), None]);
+    it::assert_equal((0..4).pad_to_stride(2), (0..4).map(Some));
+    it::assert_equal((0..1).pad_to_stride(3), vec![Some(0), None, None]);
+    assert_eq!((0..0).pad_to_stride(3).next(), None);
+}
+
+#[test]
+fn mean_variance() {
+    let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+    let (mean, var) = data.iter().cloned().mean_variance().unwrap();
+    assert!((mean - 5.0).abs() < 1e-12);
+    assert!((var - 32. / 7.).abs() < 1e-12);
+
+    assert_eq!(vec![3.5f32].into_iter().mean_variance(), Some((3.5, 0.)));
+    assert_eq!((0..0).map(|x| x as f64).mean_variance(), None);
+
+    // large offsets do not cancel out
+    let (_, var) = [1e9 + 4., 1e9 + 7., 1e9 + 13., 1e9 + 16.].iter().cloned()
+                                                              .mean_variance().unwrap();
+    assert!((var - 30.).abs() < 1e-6);
+}
+
+#[test]
+fn run_spans() {
+    let xs = ["a", "a", "b", "a", "a", "a"];
+    let it = xs.iter().run_spans(|&&x| x);
+    it::assert_equal(it, vec![("a", 0, 2), ("b", 2, 3), ("a", 3, 6)]);
+
+    let it = (0..10).run_spans(|x| x / 4);
+    it::assert_equal(it, vec![(0, 0, 4), (1, 4, 8), (2, 8, 10)]);
+    assert_eq!((0..0).run_spans(|&x| x).next(), None);
+}
+
+#[test]
+fn kmerge() {
+    let its = vec![vec![3, 8, 9], vec![0, 2, 4], vec![], vec![1, 5, 5, 10]];
+    let mut sorted = its.iter().flat_map(|v| v.iter().cloned()).collect_vec();
+    sorted.sort();
+    let it = it::kmerge(its);
+    assert_eq!(it.size_hint(), (10, Some(10)));
+    it::assert_equal(it, sorted);
+
+    let empty: Vec<Vec<f64>> = vec![];
+    assert_eq!(it::kmerge(empty).next(), None);
+
+    let mut it = it::kmerge(vec![vec![1, 4, 7], vec![2, 5], vec![3, 6, 8, 9]]);
+    assert_eq!(it.next(), Some(1));
+    assert_eq!(it.size_hint(), (8, Some(8)));
+    it::assert_equal(it, 2..10);
+}
+
+#[test]
+fn kmerge_by_key() {
+    let a = vec![(1, "a1"), (5, "a5"), (9, "a9")];
+    let b = vec![(2, "b2"), (3, "b3")];
+    let c = vec![(0, "c0"), (5, "c5"), (10, "c10")];
+    let it = it::kmerge_by_key(vec![a, b, c], |x| x.0);
+    assert_eq!(it.size_hint(), (8, Some(8)));
+    it::assert_equal(it.map(|x| x.1), vec!["c0", "a1", "b2", "b3", "a5", "c5", "a9", "c10"]);
+
+    let empty: Vec<Vec<i32>> = vec![vec![], vec![]];
+    assert_eq!(it::kmerge_by_key(empty, |&x| x).next(), None);
+    it::assert_equal(it::kmerge_by_key(vec![vec![3, 2, 1], vec![2, 1]], |&x| -x),
+                     vec![3, 2, 2, 1, 1]);
+}
+
+#[test]
+fn product_diagonal() {
+    let it = (0..).product_diagonal(0..);
+    it::assert_equal(it.take(6), vec![(0, 0), (0, 1), (1, 0), (0, 2), (1, 1), (2, 0)]);
+
+    let it = (0..3).product_diagonal(0..2);
+    assert_eq!(it.size_hint(), (6, Some(6)));
+    it::assert_equal(it, vec![(0, 0), (0, 1), (1, 0), (1, 1), (2, 0), (2, 1)]);
+
+    let it = (0..2).product_diagonal(0..3);
+    it::assert_equal(it, vec![(0, 0), (0, 1), (1, 0), (0, 2), (1, 1), (1, 2)]);
+
+    // an infinite axis does not starve the other
+    let it = (0..).product_diagonal(0..1);
+    it::assert_equal(it.take(3), vec![(0, 0), (1, 0), (2, 0)]);
+
+    assert_eq!((0..0).product_diagonal(0..).next(), None);
+    assert_eq!((0..).product_diagonal(0..0).next(), None);
+}
+
+#[test]
+fn last_n() {
+    use std::usize;
+    it::assert_equal((0..1000).last_n(3), vec![997, 998, 999]);
+    it::assert_equal((0..2).last_n(3), vec![0, 1]);
+    assert_eq!((0..5).last_n(0).next(), None);
+    assert_eq!((0..5).last_n(5).len(), 5);
+    it::assert_equal((0..5).last_n(usize::MAX), 0..5);
+}
+
+#[test]
+fn palindrome() {
+    let it = [1, 2, 3].iter().cloned().palindrome();
+    assert_eq!(it.len(), 5);
+    it::assert_equal(it, vec![1, 2, 3, 2, 1]);
+    it::assert_equal("ab".chars().palindrome(), "aba".chars());
+    it::assert_equal((0..1).palindrome(), vec![0]);
+    assert_eq!((0..0).palindrome().next(), None);
+}
+
+#[test]
+fn window_distinct_count() {
+    use std::usize;
+    let it = [1, 1, 2, 3, 3].iter().window_distinct_count(3);
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    it::assert_equal(it, vec![2, 3, 2]);
+    it::assert_equal([5, 5, 5, 5].iter().window_distinct_count(2), vec![1, 1, 1]);
+    it::assert_equal((0..4).window_distinct_count(1), vec![1, 1, 1, 1]);
+    assert_eq!((0..2).window_distinct_count(3).next(), None);
+    assert_eq!((0..3).window_distinct_count(usize::MAX).next(), None);
+}
+
+#[test]
+fn map_triples() {
+    let it = [1, 4, 9, 16].iter().cloned().map_triples(|a, b, c| a - 2 * b + c);
+    assert_eq!(it.len(), 2);
+    it::assert_equal(it, vec![2, 2]);
+
+    let it = (0..5).map_triples(|&a, &b, &c| (a, b, c));
+    it::assert_equal(it, vec![(0, 1, 2), (1, 2, 3), (2, 3, 4)]);
+    assert_eq!((0..2).map_triples(|_, _, _| ()).next(), None);
+}
+
+#[test]
+fn coalesce() {
+    // sum runs of equal numbers, keeping the run's value alongside the sum
+    let xs = [1, 1, 2, 2, 2, 3, 1];
+    let it = xs.iter().map(|&x| (x, x)).coalesce(|a, b| {
+        if a.0 == b.0 {
+            Ok((a.0, a.1 + b.1))
+        } else {
+            Err((a, b))
+        }
+    });
+    it::assert_equal(it.map(|x| x.1), vec![2, 6, 3, 1]);
+
+    let it = (0..0).coalesce(|a, b| Ok(a + b));
+    assert_eq!(it.count(), 0);
+    it::assert_equal((1..5).coalesce(|a, b| Ok(a + b)), vec![10]);
+}
+
+#[test]
+fn intersperse_cycle() {
+    let it = ['a', 'b', 'c', 'd'].iter().cloned().intersperse_cycle(vec!['-', '_']);
+    assert_eq!(it.size_hint(), (7, Some(7)));
+    it::assert_equal(it, "a-b_c-d".chars());
+    it::assert_equal((0..3).intersperse_cycle(vec![9]), (0..3).intersperse(9));
+    assert_eq!((0..0).intersperse_cycle(vec![9]).next(), None);
+}
+
+#[test]
+fn chunks() {
+    let it = (0..7).chunks(3);
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    it::assert_equal(it, vec![vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+    it::assert_equal((0..4).chunks(2), vec![vec![0, 1], vec![2, 3]]);
+    assert_eq!((0..0).chunks(2).next(), None);
+}
+
+#[test]
+fn overlap() {
+    assert_eq!(it::overlap(vec![1, 2, 3], vec![2, 3, 4]), (2, 4));
+    assert_eq!(it::overlap(vec![1, 1, 1], vec![1]), (1, 1));
+    assert_eq!(it::overlap("abc".chars(), "xyz".chars()), (0, 6));
+    assert_eq!(it::overlap(Vec::<i32>::new(), vec![]), (0, 0));
+}
+
+#[test]
+fn tuple_windows() {
+    let it = [1, 2, 3, 4].iter().cloned().tuple_windows::<(_, _)>();
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    it::assert_equal(it, vec![(1, 2), (2, 3), (3, 4)]);
+
+    let it = (0..6).tuple_windows::<(_, _, _, _)>();
+    it::assert_equal(it, vec![(0, 1, 2, 3), (1, 2, 3, 4), (2, 3, 4, 5)]);
+    assert_eq!((0..2).tuple_windows::<(_, _, _)>().next(), None);
+}
+
+#[test]
+fn take_while_fold() {
+    let it = (0..).take_while_fold(0, |sum, &x| sum + x, |&sum| sum < 10);
+    it::assert_equal(it, vec![0, 1, 2, 3]);
+
+    let mut it = (0..5).take_while_fold(0, |n, _| n + 1, |_| true);
+    assert_eq!(it.size_hint(), (0, Some(5)));
+    assert_eq!(it.by_ref().count(), 5);
+    assert_eq!(it.next(), None);
+
+    let mut it = vec![3, 1, 4].into_iter().take_while_fold(0, |sum, &x| sum + x, |&sum| sum < 3);
+    assert_eq!(it.next(), None);
+    assert_eq!(it.size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn zip_eq() {
+    let it = [1, 2, 3].iter().zip_eq(&[4, 5, 6]);
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    it::assert_equal(it, vec![(&1, &4), (&2, &5), (&3, &6)]);
+    assert_eq!((0..0).zip_eq(0..0).next(), None);
+}
+
+#[test]
+#[should_panic]
+fn zip_eq_panic() {
+    (0..3).zip_eq(0..2).count();
+}
+
+#[test]
+fn powerset_gray() {
+    let it = ["a", "b"].iter().cloned().powerset_gray();
+    assert_eq!(it.size_hint(), (4, Some(4)));
+    it::assert_equal(it, vec![vec![], vec!["a"], vec!["a", "b"], vec!["b"]]);
+
+    let subsets = (0..4).powerset_gray().collect_vec();
+    assert_eq!(subsets.len(), 16);
+    assert_eq!(subsets.iter().cloned().count_distinct(), 16);
+    for pair in subsets.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        let a_len = a.len() as isize;
+        let b_len = b.len() as isize;
+        assert_eq!((a_len - b_len).abs(), 1);
+        let (small, large) = if a.len() < b.len() { (a, b) } else { (b, a) };
+        assert!(small.iter().all(|x| large.contains(x)));
+    }
+    it::assert_equal((0..0).powerset_gray(), vec![vec![]]);
+}
+
+#[test]
+fn zip_with() {
+    let mut counter = 0;
+    let it = ["x", "y", "z"].iter().zip_with(|| { counter += 1; counter });
+    assert_eq!(it.len(), 3);
+    it::assert_equal(it, vec![(&"x", 1), (&"y", 2), (&"z", 3)]);
+    // the generator is not called past the end
+    assert_eq!(counter, 3);
+}
+
+#[test]
+fn parse_records() {
+    fn read_triple<I: Iterator<Item=i32>>(it: &mut it::PutBack<I>) -> Option<(i32, i32, i32)> {
+        match (it.next(), it.next(), it.next()) {
+            (Some(a), Some(b), Some(c)) => Some((a, b, c)),
+            _ => None,
+        }
+    }
+
+    let data = vec![2, 1, 2, 3, 4, 5, 6];
+    let parsed = data.into_iter().parse_records(|it| it.next(), read_triple);
+    assert_eq!(parsed, Ok((2, vec![(1, 2, 3), (4, 5, 6)])));
+
+    let data = vec![2, 1, 2, 3, 4];
+    let parsed = data.into_iter().parse_records(|it| it.next(), read_triple);
+    assert_eq!(parsed, Err(it::ParseError::InvalidRecord(1)));
+
+    let parsed = (0..0).parse_records(|it| it.next(), read_triple);
+    assert_eq!(parsed, Err(it::ParseError::InvalidHeader));
+
+    // the header reader can look ahead and put back
+    let data = vec![0, 0, 7, 8, 9];
+    let parsed = data.into_iter().parse_records(|it| {
+        let mut zeros = 0;
+        while let Some(x) = it.next() {
+            if x != 0 {
+                it.put_back(x);
+                break;
+            }
+            zeros += 1;
+        }
+        Some(zeros)
+    }, read_triple);
+    assert_eq!(parsed, Ok((2, vec![(7, 8, 9)])));
+}
+
+#[test]
+fn lis_len() {
+    assert_eq!([3, 1, 4, 1, 5, 9, 2, 6].iter().lis_len(), 4);
+    assert_eq!((0..0).lis_len(), 0);
+    assert_eq!((0..10).lis_len(), 10);
+    assert_eq!((0..10).rev().lis_len(), 1);
+    assert_eq!([2, 2, 2].iter().lis_len(), 1);
+}
+
+#[test]
+fn with_position() {
+    use it::Position::*;
+    it::assert_equal((0..0).with_position(), vec![]);
+    it::assert_equal((0..1).with_position(), vec![Only(0)]);
+    it::assert_equal((0..2).with_position(), vec![First(0), Last(1)]);
+    it::assert_equal((0..3).with_position(), vec![First(0), Middle(1), Last(2)]);
+
+    let it = (0..3).with_position();
+    assert_eq!(it.len(), 3);
+    assert_eq!(it.map(|p| p.into_inner()).collect::<Vec<_>>(), vec![0, 1, 2]);
+}
+
+#[test]
+fn repeat_zip() {
+    let it = ['a', 'b', 'c'].iter().repeat_zip(vec![2, 0, 3]);
+    it::assert_equal(it, &['a', 'a', 'c', 'c', 'c']);
+
+    // stops at the shorter of the two
+    it::assert_equal((0..10).repeat_zip(vec![1, 2]), vec![0, 1, 1]);
+    it::assert_equal((0..2).repeat_zip(vec![1, 1, 1]), vec![0, 1]);
+
+    let mut it = (0..3).repeat_zip(vec![3, 1, 1]);
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.size_hint(), (2, None));
+}
+
+#[test]
+fn intersperse_with() {
+    let mut count = 0;
+    let it = (1..4).map(|x| x * 10).intersperse_with(|| { count += 1; count });
+    assert_eq!(it.size_hint(), (5, Some(5)));
+    it::assert_equal(it, vec![10, 1, 20, 2, 30]);
+    assert_eq!(count, 2);
+
+    let mut it = (0..0).intersperse_with(|| -> i32 { panic!() });
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+fn argsort() {
+    assert_eq!([30, 10, 20].iter().argsort(), vec![1, 2, 0]);
+    // ties keep their original order
+    assert_eq!([2, 1, 2, 1, 0].iter().argsort(), vec![4, 1, 3, 0, 2]);
+    assert_eq!((0..0).argsort(), vec![]);
+}
+
+#[test]
+fn equivalence_classes() {
+    let near = |a: &i32, b: &i32| (a - b).abs() <= 1;
+    assert_eq!(vec![1, 2, 5, 6, 3].into_iter().equivalence_classes(near),
+               vec![vec![1, 2, 3], vec![5, 6]]);
+    // related only through a later element
+    assert_eq!(vec![1, 3, 2].into_iter().equivalence_classes(near),
+               vec![vec![1, 3, 2]]);
+    assert_eq!(vec![4, 0, 8].into_iter().equivalence_classes(near),
+               vec![vec![4], vec![0], vec![8]]);
+    assert!((0..0).equivalence_classes(|_, _| true).is_empty());
+}
+
+#[test]
+fn ascending_by_key() {
+    let mut it = [1, 3, 2, 4].iter().ascending_by_key(|&&x| x);
+    assert_eq!(it.next(), Some(Ok(&1)));
+    assert_eq!(it.next(), Some(Ok(&3)));
+    assert_eq!(it.next(), Some(Err(it::OutOfOrder)));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.size_hint(), (0, Some(0)));
+
+    // equal keys are out of order
+    let v = [1, 1].iter().ascending_by_key(|&&x| x).collect_vec();
+    assert_eq!(v, vec![Ok(&1), Err(it::OutOfOrder)]);
+
+    let v = (0..4).ascending_by_key(|&x| x).collect::<Result<Vec<_>, _>>();
+    assert_eq!(v, Ok(vec![0, 1, 2, 3]));
+}
+
+#[test]
+fn to_bitset() {
+    let bits = vec![0, 3, 64, 65].into_iter().to_bitset(128);
+    assert_eq!(bits, vec![0b1001, 0b11]);
+    assert_eq!((0..0).to_bitset(65), vec![0, 0]);
+    assert_eq!((0..0).to_bitset(0), vec![]);
+    assert_eq!((0..64).to_bitset(64), vec![!0]);
+}
+
+#[test]
+#[should_panic]
+fn to_bitset_out_of_range() {
+    vec![10].into_iter().to_bitset(10);
+}
+
+#[test]
+fn dedup_window() {
+    use std::usize;
+    let it = [1, 2, 1, 3, 1].iter().dedup_window(2);
+    it::assert_equal(it, &[1, 2, 3, 1]);
+
+    let xs = [0, 1, 0, 2, 1, 0, 0];
+    it::assert_equal(xs.iter().dedup_window(0), &xs);
+    it::assert_equal(xs.iter().dedup_window(1), xs.iter().dedup());
+    it::assert_equal(xs.iter().dedup_window(10), xs.iter().unique());
+    it::assert_equal(xs.iter().dedup_window(usize::MAX), xs.iter().unique());
+}
+
+#[test]
+fn pairwise() {
+    let it = [1, 4, 9, 16].iter().pairwise(|a, b| b - a);
+    assert_eq!(it.len(), 3);
+    it::assert_equal(it, vec![3, 5, 7]);
+
+    assert_eq!((0..1).pairwise(|a, b| (a, b)).next(), None);
+    assert_eq!((0..0).pairwise(|a, b| (a, b)).size_hint(), (0, Some(0)));
+    it::assert_equal((0..3).pairwise(|a, b| (a, b)), vec![(0, 1), (1, 2)]);
+}
+
+#[test]
+fn repeat_call() {
+    let mut counter = 0;
+    let calls = it::RepeatCall::new(|| { counter += 1; counter });
+    assert_eq!(calls.size_hint(), (usize::max_value(), None));
+    let it = (0..4).zip(calls);
+    it::assert_equal(it, vec![(0, 1), (1, 2), (2, 3), (3, 4)]);
+}
+
+#[test]
+fn multi_cartesian_product() {
+    let it = it::multi_cartesian_product(vec![vec![0, 1], vec![0, 1], vec![0, 1]]);
+    assert_eq!(it.size_hint(), (8, Some(8)));
+    let rows = it.collect_vec();
+    assert_eq!(rows.len(), 8);
+    assert_eq!(rows[0], vec![0, 0, 0]);
+    assert_eq!(rows[1], vec![0, 0, 1]);
+    assert_eq!(rows[6], vec![1, 1, 0]);
+    assert_eq!(rows[7], vec![1, 1, 1]);
+    it::assert_equal(rows, iproduct!(0..2, 0..2, 0..2).map(|(a, b, c)| vec![a, b, c]));
+
+    let mut it = it::multi_cartesian_product(vec![0..2, 0..3]);
+    it.next();
+    assert_eq!(it.size_hint(), (5, Some(5)));
+    assert_eq!(it.count(), 5);
+
+    let empty: Vec<Vec<i32>> = vec![];
+    assert_eq!(it::multi_cartesian_product(empty).next(), None);
+    assert_eq!(it::multi_cartesian_product(vec![0..2, 0..0]).next(), None);
+}
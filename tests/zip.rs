@@ -69,6 +69,17 @@ fn ziptrusted_1() {
                              .map(|((a, b), c)| (a, b, c)));
 }
 
+#[cfg(feature = "unstable")]
+#[test]
+fn ziptrusted_shortest() {
+    let xs = [1, 2, 3];
+    let mut it = ZipTrusted::new((0..10, xs.iter()));
+    assert_eq!(it.len(), 3);
+    assert_eq!(it.next(), Some((0, &1)));
+    assert_eq!(it.size_hint(), (2, Some(2)));
+    itertools::assert_equal(it, vec![(1, &2), (2, &3)]);
+}
+
 #[test]
 fn zipslices() {
     use itertools::ZipSlices;